@@ -0,0 +1,48 @@
+// Generates a test for every part of every worked example in the days'
+// examples.toml files. The tests themselves live in src/examples.rs.
+
+extern crate toml;
+
+use std::env;
+use std::fs;
+use std::fmt::Write;
+use std::path::Path;
+
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("..");
+    // new days get registered here, so it's a good sign to look again
+    println!("cargo:rerun-if-changed=src/days/mod.rs");
+
+    let mut days = fs::read_dir(&root).unwrap()
+                      .filter_map(|entry| entry.ok())
+                      .filter_map(|entry| {
+                          let name = entry.file_name().into_string().ok()?;
+                          let day = name.trim_start_matches("aoc2016-").parse::<u32>().ok()?;
+                          Some((day, entry.path().join("examples.toml")))
+                      })
+                      .filter(|(_, path)| path.exists())
+                      .collect::<Vec<_>>();
+    days.sort();
+
+    let mut out = String::new();
+    for (day, path) in days {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let text = fs::read_to_string(&path).unwrap();
+        let value = text.parse::<toml::Value>()
+                        .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        let examples = match value.get("example").and_then(|e| e.as_array()) {
+            Some(e) => e.clone(),
+            None => continue,
+        };
+        for (index, example) in examples.iter().enumerate() {
+            let slow = example.get("slow").and_then(|s| s.as_bool()) == Some(true);
+            for part in 1..3 {
+                if example.get(format!("part{}", part)).is_none() { continue; }
+                if slow { out.push_str("#[ignore]\n"); }
+                writeln!(out, "#[test]\nfn day{}_part{}_example{}() {{ super::check({}, {}, {}); }}\n",
+                         day, part, index + 1, day, index, part).unwrap();
+            }
+        }
+    }
+    fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs"), out).unwrap();
+}
@@ -0,0 +1,69 @@
+// The registry of known correct answers, kept in answers.toml at the repo root.
+// Answers are keyed by day and then by input file, relative to the day's
+// directory:
+//
+//     [day1."input.txt"]
+//     part1 = "181"
+//     part2 = "140"
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use toml;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Parts {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Parts {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    days: BTreeMap<String, BTreeMap<String, Parts>>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        toml::from_str(text)
+            .map(|days| Answers { days })
+            .map_err(|e| format!("Invalid answers: {}", e))
+    }
+
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let mut text = String::new();
+        File::open(path).and_then(|mut f| f.read_to_string(&mut text))
+                        .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?;
+        Answers::parse(&text)
+    }
+
+    // The recorded inputs for a day and their answers, ordered by file name
+    pub fn for_day(&self, day: u32) -> Vec<(&str, &Parts)> {
+        match self.days.get(&format!("day{}", day)) {
+            Some(inputs) => inputs.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn test_parse() {
+    let answers = Answers::parse(
+        "[day1.\"input.txt\"]\npart1 = \"5\"\npart2 = \"4\"\n\
+         [day2.\"input.txt\"]\npart1 = \"1985\"\n").unwrap();
+    assert_eq!(answers.for_day(1)[0].1.get(2), Some("4"));
+    assert_eq!(answers.for_day(2)[0].1.get(2), None);
+    assert!(answers.for_day(3).is_empty());
+    assert!(Answers::parse("[day1.\"input.txt\"]\npart1 = 5\n").is_err());
+}
@@ -0,0 +1,135 @@
+// `aoc batch` runs each day over all of its inputs in parallel and prints a
+// matrix of the answers. Besides input.txt a day can keep any number of named
+// inputs in an inputs/ directory, e.g. inputs/alice.txt, with their answers
+// recorded in answers.toml as [day1."inputs/alice.txt"].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use answers::Answers;
+use days::Day;
+use run::{run, Run};
+
+// input.txt followed by everything in inputs/, named relative to the day's
+// directory
+pub fn inputs(root: &Path, day: &Day) -> Vec<(String, PathBuf)> {
+    let dir = root.join(day.dir);
+    let mut inputs = Vec::new();
+    if dir.join("input.txt").is_file() {
+        inputs.push(("input.txt".to_string(), dir.join("input.txt")));
+    }
+    let mut named = fs::read_dir(dir.join("inputs")).into_iter()
+                       .flatten()
+                       .filter_map(|entry| entry.ok())
+                       .map(|entry| entry.path())
+                       .filter(|path| path.is_file())
+                       .collect::<Vec<_>>();
+    named.sort();
+    for path in named {
+        let name = format!("inputs/{}", path.file_name().unwrap().to_string_lossy());
+        inputs.push((name, path));
+    }
+    inputs
+}
+
+struct Job<'a> {
+    day: &'a Day,
+    name: String,
+    path: PathBuf,
+}
+
+// The answer followed by ✓ if it matches answers.toml, ✗ if it doesn't and ?
+// if there's nothing recorded
+fn cell(result: &Result<Run, String>, expected: Option<&str>, part: usize) -> String {
+    let answer = match *result {
+        Ok(ref r) => &r.parts[part - 1].answer,
+        Err(_) => return "parse error".to_string(),
+    };
+    match (answer, expected) {
+        (Ok(a), Some(e)) if a == e => format!("{} ✓", a),
+        (Ok(a), Some(_)) => format!("{} ✗", a),
+        (Ok(a), None) => format!("{} ?", a),
+        (Err(_), _) => "panicked".to_string(),
+    }
+}
+
+fn print_matrix(rows: &[Vec<String>]) {
+    let widths = (0..rows[0].len())
+        .map(|col| rows.iter().map(|r| r[col].chars().count()).max().unwrap())
+        .collect::<Vec<_>>();
+    for (i, row) in rows.iter().enumerate() {
+        let line = row.iter().zip(&widths)
+                      .map(|(cell, &w)| format!("{}{}", cell, " ".repeat(w - cell.chars().count())))
+                      .collect::<Vec<_>>()
+                      .join(" | ");
+        println!("{}", line.trim_end());
+        if i == 0 {
+            println!("{}", widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>().join("-|-"));
+        }
+    }
+}
+
+// Returns the number of failures: answers that don't match answers.toml, and
+// parts that couldn't be run because the input didn't parse or the solver
+// panicked
+pub fn batch(root: &Path, days: &[Day], answers: &Answers) -> usize {
+    let jobs = days.iter()
+                   .flat_map(|day| inputs(root, day).into_iter()
+                                                    .map(move |(name, path)| Job { day, name, path }))
+                   .collect::<Vec<_>>();
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(jobs.len());
+
+    // each worker takes the next job until they're all done
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (jobs, next) = (&jobs, &next);
+            s.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let job = match jobs.get(index) {
+                    Some(j) => j,
+                    None => break,
+                };
+                let result = fs::read_to_string(&job.path)
+                                 .map_err(|e| e.to_string())
+                                 .and_then(|text| run(&*job.day.solver, &text));
+                tx.send((index, result)).unwrap();
+            });
+        }
+    });
+    drop(tx);
+    let mut results = rx.into_iter().collect::<Vec<_>>();
+    results.sort_by_key(|r| r.0);
+
+    let mut failures = 0;
+    let mut rows = vec![vec!["day".to_string(), "input".to_string(),
+                             "part 1".to_string(), "part 2".to_string()]];
+    for (index, result) in results {
+        let job = &jobs[index];
+        let expected = answers.for_day(job.day.number).into_iter()
+                              .find(|&(name, _)| name == job.name)
+                              .map(|(_, parts)| parts);
+        let mut row = vec![job.day.number.to_string(), job.name.clone()];
+        for part in 1..3 {
+            let cell = cell(&result, expected.and_then(|e| e.get(part as u8)), part);
+            if !cell.ends_with('✓') && !cell.ends_with('?') { failures += 1; }
+            row.push(cell);
+        }
+        rows.push(row);
+    }
+    print_matrix(&rows);
+    failures
+}
+
+#[test]
+fn test_inputs() {
+    let day = ::days::all().remove(5);
+    let names = inputs(&::root(), &day).into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(names[0], "input.txt");
+    assert!(names.contains(&"inputs/simulated.txt".to_string()));
+}
@@ -0,0 +1,240 @@
+// `aoc bench` times parse, part 1 and part 2 separately for every day on its
+// input.txt and compares the medians with a saved baseline, much like
+// criterion does. Baselines live in aoc/target/baselines/NAME.toml.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use toml;
+
+use days::Day;
+use run::panic_message;
+
+pub struct Options {
+    // keep sampling each step until this much time has been spent on it...
+    pub budget: Duration,
+    // ...or this many samples have been taken
+    pub max_samples: usize,
+    // compare against this baseline
+    pub baseline: String,
+    // and save the new timings under this name, if set, which they aren't
+    // unless asked for so the baseline stays put
+    pub save: Option<String>,
+    // percent change in the median that counts as a regression
+    pub threshold: f64,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            budget: Duration::from_secs(3),
+            max_samples: 50,
+            baseline: "base".to_string(),
+            save: None,
+            threshold: 10.0,
+        }
+    }
+}
+
+// median nanoseconds for each step of each day, e.g. day1 -> part2 -> 12345
+type Baseline = BTreeMap<String, BTreeMap<String, u64>>;
+
+fn baseline_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("target/baselines").join(format!("{}.toml", name))
+}
+
+fn load_baseline(name: &str) -> Result<Option<Baseline>, String> {
+    read_baseline(&baseline_path(name))
+}
+
+// None if there's no baseline yet, but one that can't be read is an error
+// rather than being quietly replaced
+fn read_baseline(path: &Path) -> Result<Option<Baseline>, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Couldn't read '{}': {}", path.display(), e)),
+    };
+    toml::from_str(&text).map(Some)
+        .map_err(|e| format!("Invalid baseline '{}': {}", path.display(), e))
+}
+
+fn save_baseline(name: &str, baseline: &Baseline) -> Result<(), String> {
+    let path = baseline_path(name);
+    let text = toml::to_string(baseline).map_err(|e| e.to_string())?;
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| fs::write(&path, text))
+        .map_err(|e| format!("Couldn't write '{}': {}", path.display(), e))
+}
+
+pub struct Stats {
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub samples: usize,
+}
+
+// Summarise sample times in nanoseconds
+pub fn stats(mut samples: Vec<f64>) -> Stats {
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = samples.len();
+    let median = if n % 2 == 1 {
+        samples[n / 2]
+    } else {
+        (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+    };
+    Stats { median, min: samples[0], max: samples[n - 1], samples: n }
+}
+
+// Warm up for a little while, then time `f` until the budget or sample limit
+// is reached. Always takes at least one sample, unless `f` panics.
+fn measure<T, F: FnMut() -> T>(options: &Options, mut f: F) -> Result<Stats, String> {
+    let mut f = || panic::catch_unwind(panic::AssertUnwindSafe(&mut f)).map_err(panic_message);
+    let warm_up = Instant::now();
+    while warm_up.elapsed() < options.budget / 10 {
+        black_box(f()?);
+    }
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.is_empty() ||
+          (samples.len() < options.max_samples && start.elapsed() < options.budget) {
+        let sample = Instant::now();
+        black_box(f()?);
+        samples.push(sample.elapsed().as_nanos() as f64);
+    }
+    Ok(stats(samples))
+}
+
+pub fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n < 1e3 => format!("{:.0} ns", n),
+        n if n < 1e6 => format!("{:.1} µs", n / 1e3),
+        n if n < 1e9 => format!("{:.2} ms", n / 1e6),
+        n => format!("{:.2} s", n / 1e9),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    Regressed(f64),
+    Improved(f64),
+    Unchanged(f64),
+}
+
+pub fn compare(old: f64, new: f64, threshold: f64) -> Change {
+    let percent = (new - old) / old * 100.0;
+    if percent > threshold {
+        Change::Regressed(percent)
+    } else if percent < -threshold {
+        Change::Improved(percent)
+    } else {
+        Change::Unchanged(percent)
+    }
+}
+
+// Returns the number of regressions against the baseline
+pub fn bench(root: &Path, days: &[Day], options: &Options) -> Result<usize, String> {
+    let old = load_baseline(&options.baseline)?;
+    if old.is_none() {
+        println!("No '{}' baseline yet, nothing to compare with (--save records one)", options.baseline);
+    }
+    // days that aren't run this time keep their old timings
+    let mut new = match options.save {
+        Some(ref name) => load_baseline(name)?.unwrap_or_default(),
+        None => Baseline::new(),
+    };
+    let mut regressions = 0;
+
+    for day in days {
+        let path = root.join(day.dir).join("input.txt");
+        let text = match fs::read_to_string(&path) {
+            Ok(t) => t,
+            Err(e) => {
+                println!("day {}: skipped, couldn't read '{}': {}", day.number, path.display(), e);
+                continue;
+            }
+        };
+        let parsed = panic::catch_unwind(panic::AssertUnwindSafe(|| day.solver.parse(&text)));
+        let input = match parsed.map_err(panic_message) {
+            Ok(Ok(i)) => i,
+            Err(e) | Ok(Err(e)) => {
+                println!("day {}: skipped, {}", day.number, e);
+                continue;
+            }
+        };
+
+        let key = format!("day{}", day.number);
+        for step in &["parse", "part1", "part2"] {
+            let stats = match *step {
+                "parse" => measure(options, || day.solver.parse(&text)),
+                "part1" => measure(options, || day.solver.solve(&*input, 1)),
+                _ => measure(options, || day.solver.solve(&*input, 2)),
+            };
+            let stats = match stats {
+                Ok(s) => s,
+                Err(e) => {
+                    println!("day {} {:<5} panicked: {}", day.number, step, e);
+                    continue;
+                }
+            };
+            let change = old.as_ref()
+                            .and_then(|b| b.get(&key))
+                            .and_then(|steps| steps.get(*step))
+                            .map(|&old| compare(old as f64, stats.median, options.threshold));
+            let change = match change {
+                Some(Change::Regressed(p)) => { regressions += 1; format!("{:+.1}% REGRESSED", p) }
+                Some(Change::Improved(p)) => format!("{:+.1}% improved", p),
+                Some(Change::Unchanged(p)) => format!("{:+.1}% no change", p),
+                None => String::new(),
+            };
+            let line = format!("day {} {:<5} {:>10} (min {}, max {}, {} samples) {}",
+                               day.number, step, format_nanos(stats.median), format_nanos(stats.min),
+                               format_nanos(stats.max), stats.samples, change);
+            println!("{}", line.trim_end());
+            new.entry(key.clone()).or_default().insert(step.to_string(), stats.median as u64);
+        }
+    }
+
+    if let Some(ref name) = options.save {
+        save_baseline(name, &new)?;
+    }
+    Ok(regressions)
+}
+
+#[test]
+fn test_stats() {
+    let s = stats(vec![5.0, 1.0, 3.0]);
+    assert_eq!((s.median, s.min, s.max, s.samples), (3.0, 1.0, 5.0, 3));
+    assert_eq!(stats(vec![4.0, 1.0, 2.0, 3.0]).median, 2.5);
+}
+
+#[test]
+fn test_compare() {
+    assert_eq!(compare(100.0, 120.0, 10.0), Change::Regressed(20.0));
+    assert_eq!(compare(100.0, 80.0, 10.0), Change::Improved(-20.0));
+    assert_eq!(compare(100.0, 105.0, 10.0), Change::Unchanged(5.0));
+}
+
+#[test]
+fn test_measure() {
+    let options = Options { budget: Duration::from_millis(10), max_samples: 5, ..Options::default() };
+    assert_eq!(measure(&options, || 1).map(|s| s.samples), Ok(5));
+    assert_eq!(measure(&options, || -> u8 { panic!("not yet") }).map(|s| s.samples), Err("not yet".to_string()));
+}
+
+#[test]
+fn test_read_baseline() {
+    let path = ::std::env::temp_dir().join(format!("aoc-baseline-{}.toml", ::std::process::id()));
+    let _ = fs::remove_file(&path);
+    assert_eq!(read_baseline(&path), Ok(None));
+    fs::write(&path, "[day1]\nparse = 12\n").unwrap();
+    assert_eq!(read_baseline(&path).unwrap().unwrap()["day1"]["parse"], 12);
+    fs::write(&path, "[day1]\nparse = \"slow\"\n").unwrap();
+    assert!(read_baseline(&path).unwrap_err().starts_with("Invalid baseline"));
+    fs::remove_file(&path).unwrap();
+}
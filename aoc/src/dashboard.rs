@@ -0,0 +1,263 @@
+// `aoc dash` is a terminal dashboard listing every day with the live status of
+// its parse and both parts on input.txt, each answer marked against
+// answers.toml, and the selected day's description alongside. Each day runs on
+// its own thread, so the slow day 5 search can carry on while others run.
+
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{self, Event as Input, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use answers::Answers;
+use days::Day;
+use run::{format_duration, panic_message};
+
+const STEPS: [&str; 3] = ["parse", "part 1", "part 2"];
+
+#[derive(Clone)]
+enum Status {
+    Waiting,
+    // with how a long search is going, if the day says
+    Running(Instant, Option<String>),
+    Done(Result<String, String>, Duration),
+}
+
+// Sent from the threads running each day, which is an index into the days
+enum Event {
+    Started(usize, usize),
+    Progress(usize, usize, String),
+    Finished(usize, usize, Result<String, String>, Duration),
+}
+
+struct Dashboard {
+    days: Arc<Vec<Day>>,
+    root: PathBuf,
+    // parse, part 1 and part 2 of each day
+    status: Vec<[Status; 3]>,
+    // the answers.toml answers for input.txt
+    expected: Vec<[Option<String>; 2]>,
+    descriptions: Vec<String>,
+    table: TableState,
+    scroll: u16,
+    tx: Sender<Event>,
+    rx: Receiver<Event>,
+}
+
+fn time<T, F: FnOnce() -> T>(f: F) -> (Result<T, String>, Duration) {
+    let start = Instant::now();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f)).map_err(panic_message);
+    (result, start.elapsed())
+}
+
+// Parse then solve both parts, reporting each step as it starts and finishes
+fn run_day(days: Arc<Vec<Day>>, index: usize, root: PathBuf, tx: Sender<Event>) {
+    let day = &days[index];
+    let _ = tx.send(Event::Started(index, 0));
+    let path = root.join(day.dir).join("input.txt");
+    let (input, elapsed) = time(|| {
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))
+            .and_then(|text| day.solver.parse(&text))
+    });
+    let input = match input.and_then(|i| i) {
+        Ok(i) => i,
+        Err(e) => {
+            let _ = tx.send(Event::Finished(index, 0, Err(e), elapsed));
+            return;
+        }
+    };
+    let _ = tx.send(Event::Finished(index, 0, Ok("ok".to_string()), elapsed));
+    for part in 1..3 {
+        let _ = tx.send(Event::Started(index, part));
+        let mut report = |progress| {
+            let _ = tx.send(Event::Progress(index, part, progress));
+        };
+        let (answer, elapsed) = time(|| day.solver.solve_with_progress(&*input, part as u8, &mut report));
+        let _ = tx.send(Event::Finished(index, part, answer.and_then(|a| a), elapsed));
+    }
+}
+
+// The text and colour of one step's cell
+fn cell(status: &Status, expected: Option<&str>) -> (String, Color) {
+    match *status {
+        Status::Waiting => (String::new(), Color::Reset),
+        Status::Running(start, ref progress) => {
+            let text = format!("running {:.1} s", start.elapsed().as_secs_f64());
+            match *progress {
+                Some(ref p) => (format!("{}: {}", text, p), Color::Cyan),
+                None => (text, Color::Cyan),
+            }
+        }
+        Status::Done(Err(ref e), _) => (format!("FAILED {}", e), Color::Red),
+        Status::Done(Ok(ref answer), elapsed) => {
+            let (mark, color) = match expected {
+                Some(e) if e == answer => ("✓", Color::Green),
+                Some(_) => ("✗", Color::Red),
+                None => ("?", Color::Yellow),
+            };
+            (format!("{} {} ({})", answer, mark, format_duration(elapsed)), color)
+        }
+    }
+}
+
+impl Dashboard {
+    fn new(root: &Path, days: Vec<Day>, answers: &Answers) -> Dashboard {
+        let expected = days.iter().map(|day| {
+            let parts = answers.for_day(day.number).into_iter()
+                               .find(|&(name, _)| name == "input.txt")
+                               .map(|(_, parts)| parts);
+            [parts.and_then(|p| p.get(1)).map(String::from), parts.and_then(|p| p.get(2)).map(String::from)]
+        }).collect();
+        let descriptions = days.iter().map(|day| {
+            fs::read_to_string(root.join(day.dir).join("description.txt"))
+                .unwrap_or_else(|_| "No description.txt yet".to_string())
+        }).collect();
+        let (tx, rx) = mpsc::channel();
+        Dashboard {
+            status: days.iter().map(|_| [Status::Waiting, Status::Waiting, Status::Waiting]).collect(),
+            days: Arc::new(days),
+            root: root.to_path_buf(),
+            expected,
+            descriptions,
+            table: TableState::default().with_selected(Some(0)),
+            scroll: 0,
+            tx,
+            rx,
+        }
+    }
+
+    fn start(&mut self, index: usize) {
+        if self.status[index].iter().any(|s| matches!(*s, Status::Running(..))) {
+            return;
+        }
+        self.status[index] = [Status::Waiting, Status::Waiting, Status::Waiting];
+        let (days, root, tx) = (self.days.clone(), self.root.clone(), self.tx.clone());
+        thread::spawn(move || run_day(days, index, root, tx));
+    }
+
+    fn update(&mut self) {
+        for event in self.rx.try_iter() {
+            match event {
+                Event::Started(day, step) => self.status[day][step] = Status::Running(Instant::now(), None),
+                Event::Progress(day, step, progress) => if let Status::Running(_, ref mut p) = self.status[day][step] {
+                    *p = Some(progress);
+                },
+                Event::Finished(day, step, result, elapsed) => {
+                    self.status[day][step] = Status::Done(result, elapsed)
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                                .areas(main);
+
+        let rows = self.status.iter().enumerate().map(|(i, steps)| {
+            let mut cells = vec![Cell::from(self.days[i].number.to_string())];
+            for (step, status) in steps.iter().enumerate() {
+                let (text, color) = match (step, status) {
+                    // parsing has no answer to check
+                    (0, &Status::Done(Ok(_), elapsed)) => (format!("ok ({})", format_duration(elapsed)), Color::Green),
+                    (0, _) => cell(status, None),
+                    _ => cell(status, self.expected[i][step - 1].as_deref()),
+                };
+                cells.push(Cell::from(text).style(Style::default().fg(color)));
+            }
+            Row::new(cells)
+        });
+        let header = Row::new(["Day"].iter().chain(STEPS.iter()).cloned())
+                         .style(Style::default().add_modifier(Modifier::BOLD));
+        let widths = [Constraint::Length(4), Constraint::Length(16), Constraint::Fill(1), Constraint::Fill(1)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title("Days"))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, left, &mut self.table);
+
+        let selected = self.table.selected().unwrap_or(0);
+        let description = Paragraph::new(self.descriptions[selected].as_str())
+            .block(Block::default().borders(Borders::ALL)
+                                   .title(format!("Day {} description", self.days[selected].number)))
+            .scroll((self.scroll, 0));
+        frame.render_widget(description, right);
+
+        frame.render_widget(Line::from("↑/↓ select  enter run day  a run all  PgUp/PgDn scroll  q quit"),
+                            help);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.table.select(Some(index.min(self.days.len() - 1)));
+        self.scroll = 0;
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            self.update();
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let key = match event::read()? {
+                Input::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            let selected = self.table.selected().unwrap_or(0);
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.select(selected.saturating_sub(1)),
+                KeyCode::Down | KeyCode::Char('j') => self.select(selected + 1),
+                KeyCode::Enter | KeyCode::Char('r') => self.start(selected),
+                KeyCode::Char('a') => for index in 0..self.days.len() {
+                    self.start(index);
+                },
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub fn dashboard(root: &Path, days: Vec<Day>, answers: &Answers) -> Result<(), String> {
+    if days.is_empty() {
+        return Err("No days to show".to_string());
+    }
+    let mut dashboard = Dashboard::new(root, days, answers);
+    let mut terminal = ratatui::init();
+    // ratatui's hook puts the terminal back before reporting a panic, but
+    // solvers panicking on their own threads are shown in the table instead
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            hook(info);
+        }
+    }));
+    let result = dashboard.event_loop(&mut terminal);
+    ratatui::restore();
+    result.map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_cell() {
+    let done = |answer: &str| Status::Done(Ok(answer.to_string()), Duration::from_micros(12));
+    assert_eq!(cell(&done("181"), Some("181")), ("181 ✓ (12 µs)".to_string(), Color::Green));
+    assert_eq!(cell(&done("180"), Some("181")), ("180 ✗ (12 µs)".to_string(), Color::Red));
+    assert_eq!(cell(&done("180"), None), ("180 ? (12 µs)".to_string(), Color::Yellow));
+    assert_eq!(cell(&Status::Done(Err("oops".to_string()), Duration::default()), None),
+               ("FAILED oops".to_string(), Color::Red));
+    let running = Status::Running(Instant::now(), Some("18f4____, 1.50 MH/s, ETA 2s".to_string()));
+    assert_eq!(cell(&running, None), ("running 0.0 s: 18f4____, 1.50 MH/s, ETA 2s".to_string(), Color::Cyan));
+}
@@ -0,0 +1,32 @@
+use aoc2016_1::{parse, calc_dest, calc_dest2, Step};
+
+use solution::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Step>;
+
+    fn parse(&self, text: &str) -> Result<Vec<Step>, String> {
+        parse(text)
+    }
+
+    fn part1(&self, steps: &Vec<Step>) -> Result<String, String> {
+        Ok(calc_dest(steps).distance().to_string())
+    }
+
+    fn part2(&self, steps: &Vec<Step>) -> Result<String, String> {
+        match calc_dest2(steps) {
+            Some(dest) => Ok(dest.distance().to_string()),
+            None => Err("No location is visited twice".to_string()),
+        }
+    }
+
+    fn pieces(&self, steps: &Vec<Step>) -> Option<Vec<String>> {
+        Some(steps.iter().map(|s| s.to_string()).collect())
+    }
+
+    fn separator(&self) -> &'static str {
+        ", "
+    }
+}
@@ -0,0 +1,30 @@
+use aoc2016_2::{parse, code, code_pt2, Direction};
+
+use solution::Solution;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<Direction>>;
+
+    fn parse(&self, text: &str) -> Result<Vec<Vec<Direction>>, String> {
+        parse(text)
+    }
+
+    fn part1(&self, key_vecs: &Vec<Vec<Direction>>) -> Result<String, String> {
+        Ok(code(key_vecs).iter().map(|k| k.to_string()).collect())
+    }
+
+    // the part 2 keypad has A-D on it so print the keys as hex
+    fn part2(&self, key_vecs: &Vec<Vec<Direction>>) -> Result<String, String> {
+        Ok(code_pt2(key_vecs).iter().map(|k| format!("{:X}", k)).collect())
+    }
+
+    fn comments(&self) -> &'static str {
+        "Feeling like the code is a bit verbose"
+    }
+
+    fn pieces(&self, key_vecs: &Vec<Vec<Direction>>) -> Option<Vec<String>> {
+        Some(key_vecs.iter().map(|k| k.iter().map(|d| d.to_string()).collect()).collect())
+    }
+}
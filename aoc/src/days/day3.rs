@@ -0,0 +1,25 @@
+use aoc2016_3::{parse, count_valid, count_valid_columns, Triangle};
+
+use solution::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Triangle>;
+
+    fn parse(&self, text: &str) -> Result<Vec<Triangle>, String> {
+        parse(text)
+    }
+
+    fn part1(&self, tris: &Vec<Triangle>) -> Result<String, String> {
+        Ok(count_valid(tris).to_string())
+    }
+
+    fn part2(&self, tris: &Vec<Triangle>) -> Result<String, String> {
+        Ok(count_valid_columns(tris).to_string())
+    }
+
+    fn pieces(&self, tris: &Vec<Triangle>) -> Option<Vec<String>> {
+        Some(tris.iter().map(|t| t.to_string()).collect())
+    }
+}
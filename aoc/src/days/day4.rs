@@ -0,0 +1,33 @@
+use aoc2016_4::{parse, sum_real_rooms, find_rooms, Room};
+
+use solution::Solution;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Room>;
+
+    fn parse(&self, text: &str) -> Result<Vec<Room>, String> {
+        parse(text)
+    }
+
+    fn part1(&self, rooms: &Vec<Room>) -> Result<String, String> {
+        Ok(sum_real_rooms(rooms).to_string())
+    }
+
+    // the sector of the room where North Pole objects are stored
+    fn part2(&self, rooms: &Vec<Room>) -> Result<String, String> {
+        match find_rooms(rooms, "northpoleobject").next() {
+            Some(room) => Ok(room.sector.to_string()),
+            None => Err("No room stores North Pole objects".to_string()),
+        }
+    }
+
+    fn comments(&self) -> &'static str {
+        "Didn't spend time cleaning anything up"
+    }
+
+    fn pieces(&self, rooms: &Vec<Room>) -> Option<Vec<String>> {
+        Some(rooms.iter().map(|r| r.to_string()).collect())
+    }
+}
@@ -0,0 +1,42 @@
+use crypto::md5::Md5;
+
+use aoc2016_5::{calc_password_1, calc_password_1_with_progress, calc_password_2,
+                calc_password_2_with_progress};
+use aoc2016_5::progress::summary;
+
+use solution::Solution;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    // the door id
+    type Input = String;
+
+    fn parse(&self, text: &str) -> Result<String, String> {
+        match text.trim() {
+            "" => Err("Missing door id".to_string()),
+            id => Ok(id.to_string()),
+        }
+    }
+
+    fn part1(&self, door_id: &String) -> Result<String, String> {
+        Ok(calc_password_1(Md5::new(), door_id))
+    }
+
+    fn part2(&self, door_id: &String) -> Result<String, String> {
+        Ok(calc_password_2(Md5::new(), door_id))
+    }
+
+    fn solve_with_progress(&self, door_id: &String, part: u8, report: &mut dyn FnMut(String)) -> Result<String, String> {
+        let report = |p: &_| report(summary(p));
+        match part {
+            1 => Ok(calc_password_1_with_progress(Md5::new(), door_id, report)),
+            2 => Ok(calc_password_2_with_progress(Md5::new(), door_id, report)),
+            _ => panic!("there is no part {}", part),
+        }
+    }
+
+    fn comments(&self) -> &'static str {
+        "No animation, no threads. Should add these"
+    }
+}
@@ -0,0 +1,27 @@
+use aoc2016_6::columns::{Columns, Ragged};
+
+use solution::Solution;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Columns;
+
+    fn parse(&self, text: &str) -> Result<Columns, String> {
+        let mut columns = Columns::new(Ragged::Error);
+        columns.read(text.as_bytes())?;
+        Ok(columns)
+    }
+
+    fn part1(&self, columns: &Columns) -> Result<String, String> {
+        Ok(columns.most_common())
+    }
+
+    fn part2(&self, columns: &Columns) -> Result<String, String> {
+        Ok(columns.least_common())
+    }
+
+    fn comments(&self) -> &'static str {
+        "Comes with a noisy channel simulator (`cargo run --bin simulate`)"
+    }
+}
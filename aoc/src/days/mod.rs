@@ -0,0 +1,27 @@
+use solution::Solver;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+
+pub struct Day {
+    pub number: u32,
+    // directory holding the day's crate and input.txt, relative to the repo root
+    pub dir: &'static str,
+    pub solver: Box<dyn Solver>,
+}
+
+// Every solved day, in order
+pub fn all() -> Vec<Day> {
+    vec![
+        Day { number: 1, dir: "aoc2016-1", solver: Box::new(day1::Day1) },
+        Day { number: 2, dir: "aoc2016-2", solver: Box::new(day2::Day2) },
+        Day { number: 3, dir: "aoc2016-3", solver: Box::new(day3::Day3) },
+        Day { number: 4, dir: "aoc2016-4", solver: Box::new(day4::Day4) },
+        Day { number: 5, dir: "aoc2016-5", solver: Box::new(day5::Day5) },
+        Day { number: 6, dir: "aoc2016-6", solver: Box::new(day6::Day6) },
+    ]
+}
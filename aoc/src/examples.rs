@@ -0,0 +1,58 @@
+// Worked examples from each day's description.txt, kept in examples.toml next
+// to it:
+//
+//     [[example]]
+//     input = "R2, L3"
+//     part1 = "5"
+//
+// build.rs turns every part of every example into a test. Examples marked
+// `slow = true` are ignored unless the tests are run with --ignored.
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use toml;
+
+use answers::Parts;
+
+#[derive(Debug, Deserialize)]
+pub struct Example {
+    pub input: String,
+    #[serde(flatten)]
+    pub expected: Parts,
+}
+
+#[derive(Deserialize)]
+struct Examples {
+    #[serde(default)]
+    example: Vec<Example>,
+}
+
+// A day without an examples.toml just has no examples
+pub fn load(path: &Path) -> Result<Vec<Example>, String> {
+    let mut text = String::new();
+    match File::open(path) {
+        Ok(mut f) => f.read_to_string(&mut text)
+                      .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?,
+        Err(_) => return Ok(Vec::new()),
+    };
+    toml::from_str::<Examples>(&text)
+        .map(|e| e.example)
+        .map_err(|e| format!("Invalid examples in '{}': {}", path.display(), e))
+}
+
+#[cfg(test)]
+fn check(day: u32, index: usize, part: u8) {
+    let day = ::days::all().into_iter().find(|d| d.number == day).unwrap();
+    let examples = load(&::root().join(day.dir).join("examples.toml")).unwrap();
+    let example = &examples[index];
+    let input = day.solver.parse(&example.input).unwrap();
+    assert_eq!(day.solver.solve(&*input, part).as_ref().map(|a| a.as_str()), Ok(example.expected.get(part).unwrap()),
+               "input was {:?}", example.input);
+}
+
+#[cfg(test)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -0,0 +1,283 @@
+// `aoc fetch --day N` downloads a day's puzzle input and description into its
+// directory as input.txt and description.txt. Files that are already there are
+// never downloaded again, unless they're empty like the description `aoc new`
+// leaves, and requests are spaced out so we don't hammer the site. The HTTP
+// side sits behind the Http trait so tests can use a stub, and `aoc submit`
+// shares the same client.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use ureq;
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+    // seconds the server asked us to wait, from a Retry-After header
+    pub retry_after: Option<u64>,
+}
+
+pub trait Http {
+    fn get(&self, url: &str, session: &str) -> Result<Response, String>;
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, String>;
+}
+
+pub struct Ureq {
+    agent: ureq::Agent,
+}
+
+impl Ureq {
+    pub fn new() -> Ureq {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!("aoc2016 input fetcher by ", env!("CARGO_PKG_AUTHORS")))
+            .timeout(Duration::from_secs(30))
+            .build();
+        Ureq { agent }
+    }
+}
+
+// Error statuses still have a body worth reading
+fn read(url: &str, result: Result<ureq::Response, ureq::Error>) -> Result<Response, String> {
+    let response = match result {
+        Ok(r) => r,
+        Err(ureq::Error::Status(_, r)) => r,
+        Err(e) => return Err(format!("Couldn't fetch '{}': {}", url, e)),
+    };
+    let status = response.status();
+    let retry_after = response.header("Retry-After").and_then(|s| s.trim().parse().ok());
+    let body = response.into_string().map_err(|e| format!("Couldn't read '{}': {}", url, e))?;
+    Ok(Response { status, body, retry_after })
+}
+
+impl Http for Ureq {
+    fn get(&self, url: &str, session: &str) -> Result<Response, String> {
+        read(url, self.agent.get(url).set("Cookie", &format!("session={}", session)).call())
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+        read(url, self.agent.post(url).set("Cookie", &format!("session={}", session)).send_form(form))
+    }
+}
+
+pub struct Client<H> {
+    http: H,
+    base_url: String,
+    session: String,
+    // touched after every request, so separate runs share the rate limit
+    stamp: PathBuf,
+    // leave at least this long between requests
+    pub interval: Duration,
+}
+
+impl<H: Http> Client<H> {
+    pub fn new(http: H, base_url: &str, session: &str, stamp: &Path) -> Client<H> {
+        Client {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            stamp: stamp.to_path_buf(),
+            interval: Duration::from_secs(5),
+        }
+    }
+
+    fn wait_turn(&self) -> Result<(), String> {
+        let last = fs::metadata(&self.stamp).and_then(|m| m.modified()).ok();
+        if let Some(since) = last.and_then(|t| SystemTime::now().duration_since(t).ok()) {
+            if since < self.interval {
+                thread::sleep(self.interval - since);
+            }
+        }
+        fs::create_dir_all(self.stamp.parent().unwrap())
+            .and_then(|_| fs::write(&self.stamp, ""))
+            .map_err(|e| format!("Couldn't write '{}': {}", self.stamp.display(), e))
+    }
+
+    fn get(&self, path: &str) -> Result<String, String> {
+        self.wait_turn()?;
+        let url = format!("{}{}", self.base_url, path);
+        check_status(&url, self.http.get(&url, &self.session)?)
+    }
+
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        self.wait_turn()?;
+        let url = format!("{}{}", self.base_url, path);
+        check_status(&url, self.http.post(&url, &self.session, form)?)
+    }
+
+    // Download whatever `dir` is missing of day `day`'s input and description,
+    // counting empty files as missing. Returns the names of the files written.
+    pub fn fetch(&self, day: u32, dir: &Path) -> Result<Vec<&'static str>, String> {
+        let mut written = Vec::new();
+        for &(name, path) in &[("input.txt", "/input"), ("description.txt", "")] {
+            let file = dir.join(name);
+            if fs::read_to_string(&file).map(|t| !t.trim().is_empty()).unwrap_or(false) {
+                continue;
+            }
+            let body = self.get(&format!("/2016/day/{}{}", day, path))?;
+            let text = if name == "input.txt" { body } else { html_to_text(&body) };
+            fs::write(&file, text).map_err(|e| format!("Couldn't write '{}': {}", file.display(), e))?;
+            written.push(name);
+        }
+        Ok(written)
+    }
+}
+
+fn check_status(url: &str, response: Response) -> Result<String, String> {
+    match response.status {
+        200 => Ok(response.body),
+        404 => Err(format!("'{}' isn't available yet", url)),
+        400 | 401 | 403 | 500 if response.body.contains("log in") =>
+            Err("The session token was rejected, log in again and copy a fresh one".to_string()),
+        429 => Err(format!("Rate limited, try again in {}s", response.retry_after.unwrap_or(60))),
+        status => Err(format!("'{}' returned {}: {}", url, status, response.body.trim())),
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn wrap(text: &str, width: usize) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() { line.push(' '); }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+// Turn the <article>s of a puzzle page into plain text in the style of the
+// existing description.txt files: one paragraph per block wrapped at 80
+// columns, with <pre> blocks left alone
+pub fn html_to_text(html: &str) -> String {
+    let mut blocks = Vec::new();
+    for article in html.split("<article").skip(1) {
+        let article = article.split("</article>").next().unwrap();
+        let mut rest = &article[article.find('>').map_or(0, |i| i + 1)..];
+        let mut block = String::new();
+        let mut pre = false;
+        while let Some(start) = rest.find('<') {
+            block.push_str(&rest[..start]);
+            // a tag that's never closed ends the article
+            let end = match rest[start..].find('>') {
+                Some(i) => start + i + 1,
+                None => break,
+            };
+            let tag = rest[start + 1..end - 1].split_whitespace().next().unwrap_or("");
+            match tag {
+                "pre" => pre = true,
+                "li" => block.push_str("- "),
+                "/p" | "/h2" | "/li" | "/pre" => {
+                    let text = decode_entities(&block);
+                    if pre {
+                        blocks.push(text.trim_end().to_string());
+                    } else if !text.trim().is_empty() {
+                        blocks.push(wrap(&text, 80));
+                    }
+                    block.clear();
+                    pre = false;
+                }
+                _ => {}
+            }
+            rest = &rest[end..];
+        }
+    }
+    blocks.join("\n\n") + "\n"
+}
+
+#[test]
+fn test_html_to_text() {
+    let html = "<main><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2>\
+                <p>Some <em>very</em> long text that goes on and on, well past the eighty \
+                columns that fit on a line &amp; then some.</p>\
+                <pre><code>a  b\n&lt;c&gt;\n</code></pre>\
+                <ul><li>one</li><li>two</li></ul></article><p>Answer:</p></main>";
+    assert_eq!(html_to_text(html),
+               "--- Day 1: Test ---\n\n\
+                Some very long text that goes on and on, well past the eighty columns that fit\n\
+                on a line & then some.\n\n\
+                a  b\n<c>\n\n\
+                - one\n\n\
+                - two\n");
+    assert_eq!(html_to_text("<article><p>cut off</p><"), "cut off\n");
+    assert_eq!(html_to_text("<article><p>cut off</p></art"), "cut off\n");
+}
+
+#[cfg(test)]
+pub fn stub_server(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    // answer one request per response, then stop, handing back each request
+    // line with its cookie and body
+    let server = thread::spawn(move || responses.into_iter().map(|(status, body)| {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request = Vec::new();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() { break; }
+            let line = line.trim().to_string();
+            if let Some(n) = line.to_lowercase().strip_prefix("content-length:") {
+                length = n.trim().parse().unwrap();
+            }
+            if request.is_empty() || line.to_lowercase().starts_with("cookie:") {
+                request.push(line);
+            }
+        }
+        let mut form = vec![0; length];
+        reader.read_exact(&mut form).unwrap();
+        if length > 0 {
+            request.push(String::from_utf8(form).unwrap());
+        }
+        write!(reader.get_mut(), "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+               status, body.len(), body).unwrap();
+        request.join(" ")
+    }).collect());
+    (url, server)
+}
+
+#[test]
+fn test_fetch() {
+    let dir = ::std::env::temp_dir().join(format!("aoc-fetch-{}", ::std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let (url, server) = stub_server(vec![
+        (200, "R2, L3\n"),
+        (200, "<article><h2>--- Day 1 ---</h2><p>Hello</p></article>"),
+        (429, "slow down"),
+    ]);
+    let mut client = Client::new(Ureq::new(), &url, "abc\n", &dir.join("stamp"));
+    client.interval = Duration::from_millis(0);
+
+    assert_eq!(client.fetch(1, &dir), Ok(vec!["input.txt", "description.txt"]));
+    assert_eq!(fs::read_to_string(dir.join("input.txt")).unwrap(), "R2, L3\n");
+    assert_eq!(fs::read_to_string(dir.join("description.txt")).unwrap(), "--- Day 1 ---\n\nHello\n");
+    // everything is cached now, so nothing is requested
+    assert_eq!(client.fetch(1, &dir), Ok(vec![]));
+
+    fs::write(dir.join("input.txt"), "").unwrap();
+    assert!(client.fetch(1, &dir).unwrap_err().starts_with("Rate limited"));
+
+    assert_eq!(server.join().unwrap(), vec![
+        "GET /2016/day/1/input HTTP/1.1 Cookie: session=abc",
+        "GET /2016/day/1 HTTP/1.1 Cookie: session=abc",
+        "GET /2016/day/1/input HTTP/1.1 Cookie: session=abc",
+    ]);
+    fs::remove_dir_all(&dir).unwrap();
+}
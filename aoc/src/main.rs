@@ -0,0 +1,363 @@
+extern crate crypto;
+extern crate notify;
+extern crate ratatui;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate toml;
+extern crate ureq;
+
+extern crate aoc2016_1;
+extern crate aoc2016_2;
+extern crate aoc2016_3;
+extern crate aoc2016_4;
+extern crate aoc2016_5;
+extern crate aoc2016_6;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+mod answers;
+mod batch;
+mod bench;
+mod dashboard;
+mod days;
+mod examples;
+mod fetch;
+mod minimise;
+mod readme;
+mod report;
+mod run;
+mod scaffold;
+mod solution;
+mod submit;
+mod verify;
+mod watch;
+
+use answers::Answers;
+
+fn usage() -> ! {
+    let name = env::args().next().unwrap();
+    println!("Usage: {} command [options]", &name);
+    println!("Commands:");
+    println!("  run [--day N] [--input FILE] [--format text|json]");
+    println!("                     solve each day, or one day with another input");
+    println!("  batch [--day N]    run every input of each day in parallel");
+    println!("  dash               browse and run every day in a terminal dashboard");
+    println!("  verify [--day N]   check every solver against its examples and answers.toml");
+    println!("  watch --day N      re-run a day's examples and parts whenever it changes");
+    println!("  fetch --day N [--session TOKEN]");
+    println!("                     download a day's input.txt and description.txt if missing");
+    println!("  submit --day N --part P [--session TOKEN]");
+    println!("                     send a day's answer, unless the history rules it out");
+    println!("  minimise --day N [--input FILE] (--panics | --part P --answer X | --command CMD)");
+    println!("                     shrink an input while it still fails in the given way");
+    println!("  new DAY            create a crate for a new day and register it");
+    println!("  readme             regenerate the progress table in README.md");
+    println!("  bench [--day N] [--baseline NAME] [--save | --save-baseline NAME] [--threshold PCT] [--time SECS]");
+    println!("                     time every day and compare with a saved baseline");
+    process::exit(1);
+}
+
+// The repo root, which holds answers.toml and a directory per day
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn load_answers() -> Answers {
+    Answers::load(&root().join("answers.toml")).unwrap_or_else(|e| {
+        println!("{}", e);
+        process::exit(1);
+    })
+}
+
+// A client for the puzzle site, or AOC_URL if set, using the session token
+// from --session, AOC_SESSION or the .session file
+fn client(session: Option<String>) -> fetch::Client<fetch::Ureq> {
+    let session = session.or_else(|| env::var("AOC_SESSION").ok())
+                         .or_else(|| fs::read_to_string(root().join(".session")).ok())
+                         .unwrap_or_else(|| {
+        println!("No session token, pass --session, set AOC_SESSION or put it in .session");
+        process::exit(1);
+    });
+    let url = env::var("AOC_URL").unwrap_or_else(|_| "https://adventofcode.com".to_string());
+    let stamp = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/last-fetch");
+    fetch::Client::new(fetch::Ureq::new(), &url, &session, &stamp)
+}
+
+// Parse a "--day N" option, if there is one
+fn day_option(args: &[String]) -> Option<u32> {
+    match args {
+        [] => None,
+        [flag, n] if flag == "--day" => Some(n.parse::<u32>().unwrap_or_else(|_| usage())),
+        _ => usage(),
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if args.is_empty() { usage(); }
+
+    match args[0].as_str() {
+        "run" => {
+            let mut only = None;
+            let mut input = None;
+            let mut format = report::Format::Text;
+            let mut rest = args[1..].iter();
+            while let Some(flag) = rest.next() {
+                let value = rest.next().unwrap_or_else(|| usage());
+                match flag.as_str() {
+                    "--day" => only = Some(value.parse::<u32>().unwrap_or_else(|_| usage())),
+                    "--input" => input = Some(PathBuf::from(value)),
+                    "--format" => format = value.parse().unwrap_or_else(|e| {
+                        println!("{}", e);
+                        usage();
+                    }),
+                    _ => usage(),
+                }
+            }
+            if input.is_some() && only.is_none() { usage(); }
+            let mut failed = false;
+            for day in days::all().into_iter().filter(|d| only.is_none() || only == Some(d.number)) {
+                let path = input.clone().unwrap_or_else(|| root().join(day.dir).join("input.txt"));
+                let text = fs::read_to_string(&path).unwrap_or_else(|e| {
+                    println!("Couldn't read '{}': {}", path.display(), e);
+                    process::exit(1);
+                });
+                for record in report::records(&day, &text) {
+                    failed |= record.error.is_some();
+                    report::print(&record, format);
+                }
+            }
+            if failed { process::exit(1); }
+        }
+        "verify" => {
+            let only = day_option(&args[1..]);
+            let answers = load_answers();
+            let days = days::all().into_iter()
+                                  .filter(|d| only.is_none() || only == Some(d.number))
+                                  .collect::<Vec<_>>();
+            let summary = verify::verify(&root(), &days, &answers);
+            println!("{} passed, {} failed", summary.passed, summary.failed);
+            if summary.failed > 0 { process::exit(1); }
+        }
+        "new" => {
+            if args.len() != 2 { usage(); }
+            let day = args[1].parse::<u32>().unwrap_or_else(|_| usage());
+            if let Err(e) = scaffold::new_day(&root(), day) {
+                println!("{}", e);
+                process::exit(1);
+            }
+            println!("Created aoc2016-{0} and registered it as day {0}", day);
+            println!("Fetch the puzzle with `aoc fetch --day {0}` and fill in examples.toml", day);
+        }
+        "readme" => {
+            if args.len() != 1 { usage(); }
+            if let Err(e) = readme::update(&root(), &days::all(), &load_answers()) {
+                println!("{}", e);
+                process::exit(1);
+            }
+            println!("Updated README.md");
+        }
+        "bench" => {
+            let mut options = bench::Options::default();
+            let mut only = None;
+            let mut rest = args[1..].iter();
+            let mut save = false;
+            while let Some(flag) = rest.next() {
+                if flag == "--save" {
+                    save = true;
+                    continue;
+                }
+                let value = rest.next().unwrap_or_else(|| usage());
+                match flag.as_str() {
+                    "--day" => only = Some(value.parse::<u32>().unwrap_or_else(|_| usage())),
+                    "--baseline" => options.baseline = value.clone(),
+                    "--save-baseline" => {
+                        options.baseline = value.clone();
+                        options.save = Some(value.clone());
+                    }
+                    "--threshold" => options.threshold = value.parse().unwrap_or_else(|_| usage()),
+                    "--time" => {
+                        let secs = value.parse::<f64>().unwrap_or_else(|_| usage());
+                        options.budget = std::time::Duration::from_secs_f64(secs);
+                    }
+                    _ => usage(),
+                }
+            }
+            // --save replaces the baseline that was compared with
+            if save {
+                options.save = Some(options.baseline.clone());
+            }
+            let days = days::all().into_iter()
+                                  .filter(|d| only.is_none() || only == Some(d.number))
+                                  .collect::<Vec<_>>();
+            match bench::bench(&root(), &days, &options) {
+                Ok(0) => {}
+                Ok(n) => {
+                    println!("{} regressions", n);
+                    process::exit(1);
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        "watch" => {
+            let once = args.last().map(|a| a == "--once") == Some(true);
+            let end = if once { args.len() - 1 } else { args.len() };
+            let day = match day_option(&args[1..end]) {
+                Some(n) => days::all().into_iter().find(|d| d.number == n).unwrap_or_else(|| {
+                    println!("Day {} isn't registered", n);
+                    process::exit(1);
+                }),
+                None => usage(),
+            };
+            if once {
+                let summary = watch::once(&root(), &day, &load_answers());
+                if summary.failed > 0 { process::exit(1); }
+            } else if let Err(e) = watch::watch(&root(), &day) {
+                println!("{}", e);
+                process::exit(1);
+            }
+        }
+        "batch" => {
+            let only = day_option(&args[1..]);
+            let days = days::all().into_iter()
+                                  .filter(|d| only.is_none() || only == Some(d.number))
+                                  .collect::<Vec<_>>();
+            if batch::batch(&root(), &days, &load_answers()) > 0 { process::exit(1); }
+        }
+        "fetch" => {
+            let mut day = None;
+            let mut session = None;
+            let mut rest = args[1..].iter();
+            while let Some(flag) = rest.next() {
+                let value = rest.next().unwrap_or_else(|| usage());
+                match flag.as_str() {
+                    "--day" => day = Some(value.parse::<u32>().unwrap_or_else(|_| usage())),
+                    "--session" => session = Some(value.clone()),
+                    _ => usage(),
+                }
+            }
+            let day = day.unwrap_or_else(|| usage());
+            let client = client(session);
+            let dir = root().join(format!("aoc2016-{}", day));
+            if !dir.is_dir() {
+                println!("There's no aoc2016-{0} yet, create it with `aoc new {0}` first", day);
+                process::exit(1);
+            }
+            match client.fetch(day, &dir) {
+                Ok(ref written) if written.is_empty() => println!("aoc2016-{} is up to date", day),
+                Ok(written) => for name in written {
+                    println!("Wrote aoc2016-{}/{}", day, name);
+                },
+                Err(e) => {
+                    println!("{}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        "submit" => {
+            let (mut day, mut part, mut session) = (None, None, None);
+            let mut rest = args[1..].iter();
+            while let Some(flag) = rest.next() {
+                let value = rest.next().unwrap_or_else(|| usage());
+                match flag.as_str() {
+                    "--day" => day = Some(value.parse::<u32>().unwrap_or_else(|_| usage())),
+                    "--part" => part = Some(value.parse::<u8>().unwrap_or_else(|_| usage())),
+                    "--session" => session = Some(value.clone()),
+                    _ => usage(),
+                }
+            }
+            let part = part.filter(|&p| p == 1 || p == 2).unwrap_or_else(|| usage());
+            let day = match day {
+                Some(n) => days::all().into_iter().find(|d| d.number == n).unwrap_or_else(|| {
+                    println!("Day {} isn't registered", n);
+                    process::exit(1);
+                }),
+                None => usage(),
+            };
+            let path = root().join(day.dir).join("input.txt");
+            let answer = fs::read_to_string(&path).map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))
+                             .and_then(|text| run::run(&*day.solver, &text))
+                             .and_then(|mut r| r.parts.remove(part as usize - 1).answer)
+                             .unwrap_or_else(|e| {
+                println!("{}", e);
+                process::exit(1);
+            });
+            println!("Submitting {} for day {} part {}", answer, day.number, part);
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+            match submit::submit(&client(session), &root().join("submissions.jsonl"), day.number, part, &answer, now) {
+                Ok((attempt, message)) => {
+                    println!("{}", message.trim_end());
+                    if attempt.verdict != submit::Verdict::Right { process::exit(1); }
+                    println!("Add it to answers.toml so verify keeps checking it");
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        "minimise" => {
+            let (mut day, mut input, mut part, mut answer, mut failure) = (None, None, None, None, None);
+            let mut rest = args[1..].iter();
+            while let Some(flag) = rest.next() {
+                if flag == "--panics" {
+                    failure = Some(minimise::Failure::Panics);
+                    continue;
+                }
+                let value = rest.next().unwrap_or_else(|| usage());
+                match flag.as_str() {
+                    "--day" => day = Some(value.parse::<u32>().unwrap_or_else(|_| usage())),
+                    "--input" => input = Some(PathBuf::from(value)),
+                    "--part" => part = Some(value.parse::<u8>().unwrap_or_else(|_| usage())),
+                    "--answer" => answer = Some(value.clone()),
+                    "--command" => failure = Some(minimise::Failure::Command(value.clone())),
+                    _ => usage(),
+                }
+            }
+            // --part and --answer go together, in either order, instead of
+            // --panics or --command
+            let failure = match (failure, part, answer) {
+                (None, Some(part @ 1..=2), Some(answer)) => minimise::Failure::Answer(part, answer),
+                (Some(failure), None, None) => failure,
+                _ => usage(),
+            };
+            let day = match day {
+                Some(n) => days::all().into_iter().find(|d| d.number == n).unwrap_or_else(|| {
+                    println!("Day {} isn't registered", n);
+                    process::exit(1);
+                }),
+                None => usage(),
+            };
+            let path = input.unwrap_or_else(|| root().join(day.dir).join("input.txt"));
+            let text = fs::read_to_string(&path).unwrap_or_else(|e| {
+                println!("Couldn't read '{}': {}", path.display(), e);
+                process::exit(1);
+            });
+            match minimise::minimise(&day, &text, &failure) {
+                Ok((smallest, before, after)) => {
+                    println!("Shrunk {} pieces to {}:", before, after);
+                    println!("{}", smallest);
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        "dash" => {
+            if args.len() != 1 { usage(); }
+            if let Err(e) = dashboard::dashboard(&root(), days::all(), &load_answers()) {
+                println!("{}", e);
+                process::exit(1);
+            }
+        }
+        _ => usage(),
+    }
+}
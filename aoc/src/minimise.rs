@@ -0,0 +1,142 @@
+// `aoc minimise` shrinks an input that makes a solver fail. The input is split
+// into pieces with the day's parser and Display impls (steps, key lines,
+// triangles, rooms), then delta debugging keeps dropping chunks of pieces for
+// as long as the failure still happens.
+
+use std::env;
+use std::fs;
+use std::panic;
+use std::process::Command;
+
+use days::Day;
+use run::run;
+
+pub enum Failure {
+    // parsing panics or either part fails
+    Panics,
+    // the part gives this answer
+    Answer(u8, String),
+    // this shell command succeeds when given the path of the input
+    Command(String),
+}
+
+impl Failure {
+    fn reproduces(&self, day: &Day, text: &str) -> bool {
+        match *self {
+            Failure::Panics => match panic::catch_unwind(panic::AssertUnwindSafe(|| run(&*day.solver, text))) {
+                Ok(Ok(r)) => r.parts.iter().any(|p| p.answer.is_err()),
+                // a parse error just means we cut in the wrong place
+                Ok(Err(_)) => false,
+                Err(_) => true,
+            },
+            Failure::Answer(part, ref answer) => match panic::catch_unwind(panic::AssertUnwindSafe(|| run(&*day.solver, text))) {
+                Ok(Ok(r)) => r.parts[part as usize - 1].answer.as_ref() == Ok(answer),
+                _ => false,
+            },
+            Failure::Command(ref command) => {
+                let path = env::temp_dir().join(format!("aoc-minimise-{}.txt", ::std::process::id()));
+                let fails = fs::write(&path, text).is_ok() &&
+                    Command::new("sh").arg("-c").arg(format!("{} \"$0\"", command)).arg(&path)
+                                      .status().map(|s| s.success()).unwrap_or(false);
+                let _ = fs::remove_file(&path);
+                fails
+            }
+        }
+    }
+}
+
+// Zeller's ddmin: try each of n chunks on its own, then everything but each
+// chunk, and split into smaller chunks when neither still fails. The result
+// fails but removing any one piece from it doesn't.
+pub fn ddmin<F: FnMut(&[String]) -> bool>(mut pieces: Vec<String>, mut fails: F) -> Vec<String> {
+    let mut n = 2;
+    while pieces.len() >= 2 {
+        let size = pieces.len().div_ceil(n);
+        let chunks = (0..pieces.len()).step_by(size)
+                                      .map(|start| (start, (start + size).min(pieces.len())))
+                                      .collect::<Vec<_>>();
+        let subset = chunks.iter()
+                           .map(|&(start, end)| pieces[start..end].to_vec())
+                           .find(|subset| fails(subset));
+        if let Some(subset) = subset {
+            pieces = subset;
+            n = 2;
+            continue;
+        }
+        let complement = chunks.iter()
+                               .map(|&(start, end)| [&pieces[..start], &pieces[end..]].concat())
+                               .find(|complement| fails(complement));
+        if let Some(complement) = complement {
+            pieces = complement;
+            n = (n - 1).max(2);
+            continue;
+        }
+        if n >= pieces.len() {
+            break;
+        }
+        n = (n * 2).min(pieces.len());
+    }
+    pieces
+}
+
+// Returns the smallest failing input found, and how many pieces it had before
+// and after
+pub fn minimise(day: &Day, text: &str, failure: &Failure) -> Result<(String, usize, usize), String> {
+    // the solver is expected to panic a lot, which shouldn't fill the terminal
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = minimise_quietly(day, text, failure);
+    panic::set_hook(hook);
+    result
+}
+
+fn minimise_quietly(day: &Day, text: &str, failure: &Failure) -> Result<(String, usize, usize), String> {
+    if !failure.reproduces(day, text) {
+        return Err("The input doesn't fail to begin with".to_string());
+    }
+    // the parser can panic too, and then the input is split into lines instead
+    let parsed = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        day.solver.parse(text).ok().and_then(|input| day.solver.pieces(&*input))
+    })).unwrap_or(None);
+    let (pieces, separator) = match parsed {
+        Some(pieces) => (pieces, day.solver.separator()),
+        None => (text.lines().map(String::from).collect(), "\n"),
+    };
+    let count = pieces.len();
+    let smallest = ddmin(pieces, |pieces| failure.reproduces(day, &pieces.join(separator)));
+    Ok((smallest.join(separator), count, smallest.len()))
+}
+
+#[test]
+fn test_ddmin() {
+    let pieces = (0..100).map(|i| i.to_string()).collect::<Vec<_>>();
+    let mut tests = 0;
+    let smallest = ddmin(pieces, |p| {
+        tests += 1;
+        p.contains(&"13".to_string()) && p.contains(&"77".to_string())
+    });
+    assert_eq!(smallest, vec!["13", "77"]);
+    assert!(tests < 100, "took {} tests", tests);
+}
+
+#[test]
+fn test_parse_panics() {
+    use solution::Solution;
+
+    // a day whose parser panics on any line with an x in it
+    struct Picky;
+    impl Solution for Picky {
+        type Input = Vec<String>;
+        fn parse(&self, text: &str) -> Result<Vec<String>, String> {
+            assert!(!text.contains('x'), "found an x");
+            Ok(text.lines().map(String::from).collect())
+        }
+        fn part1(&self, lines: &Vec<String>) -> Result<String, String> { Ok(lines.len().to_string()) }
+        fn part2(&self, _: &Vec<String>) -> Result<String, String> { Ok(String::new()) }
+        fn pieces(&self, lines: &Vec<String>) -> Option<Vec<String>> { Some(lines.clone()) }
+    }
+
+    let day = Day { number: 0, dir: "", solver: Box::new(Picky) };
+    assert_eq!(minimise(&day, "a\nbx\nc\nd", &Failure::Panics), Ok(("bx".to_string(), 4, 1)));
+    assert!(!Failure::Answer(1, "1".to_string()).reproduces(&day, "x"));
+}
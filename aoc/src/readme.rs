@@ -0,0 +1,86 @@
+// `aoc readme` regenerates the progress table in README.md by running every
+// registered day on its input.txt and comparing against answers.toml
+
+use std::fs;
+use std::path::Path;
+
+use answers::Answers;
+use days::Day;
+use run::{run, format_duration};
+
+const HEADER: &str = "Day | Part 1 | Part 2 | Runtime | Comments\n\
+                      ----|--------|--------|---------|---------";
+
+// ✓ matches answers.toml, ✗ doesn't, ? has no recorded answer and a blank
+// didn't produce an answer at all
+fn status(answer: &Result<String, String>, expected: Option<&str>) -> &'static str {
+    match (answer, expected) {
+        (Ok(a), Some(e)) if a == e => "✓",
+        (Ok(_), Some(_)) => "✗",
+        (Ok(_), None) => "?",
+        (Err(_), _) => " ",
+    }
+}
+
+fn row(root: &Path, day: &Day, answers: &Answers) -> String {
+    let expected = answers.for_day(day.number).into_iter()
+                          .find(|&(name, _)| name == "input.txt")
+                          .map(|(_, parts)| parts);
+    let result = fs::read_to_string(root.join(day.dir).join("input.txt"))
+                     .map_err(|e| e.to_string())
+                     .and_then(|text| run(&*day.solver, &text));
+    let (part1, part2, runtime) = match result {
+        Ok(r) => (
+            status(&r.parts[0].answer, expected.and_then(|p| p.get(1))),
+            status(&r.parts[1].answer, expected.and_then(|p| p.get(2))),
+            format_duration(r.total_time()),
+        ),
+        Err(_) => (" ", " ", String::new()),
+    };
+    let row = format!("{:<4}|   {}    |   {}    | {:<8}| {}",
+                      day.number, part1, part2, runtime, day.solver.comments());
+    row.trim_end().to_string()
+}
+
+pub fn table(root: &Path, days: &[Day], answers: &Answers) -> String {
+    let mut table = HEADER.to_string();
+    for day in days {
+        table.push('\n');
+        table.push_str(&row(root, day, answers));
+    }
+    table
+}
+
+// Swap the table starting at the "Day |" header line for a new one
+pub fn replace_table(readme: &str, table: &str) -> Result<String, String> {
+    let lines = readme.lines().collect::<Vec<_>>();
+    let start = lines.iter().position(|l| l.starts_with("Day |"))
+                     .ok_or_else(|| "Couldn't find the table in the README".to_string())?;
+    let end = lines[start..].iter().position(|l| !l.contains('|'))
+                            .map_or(lines.len(), |i| start + i);
+    let mut out = lines[..start].join("\n");
+    if start > 0 { out.push('\n'); }
+    out.push_str(table);
+    for line in &lines[end..] {
+        out.push('\n');
+        out.push_str(line);
+    }
+    out.push('\n');
+    Ok(out)
+}
+
+pub fn update(root: &Path, days: &[Day], answers: &Answers) -> Result<(), String> {
+    let path = root.join("README.md");
+    let readme = fs::read_to_string(&path)
+                     .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?;
+    let readme = replace_table(&readme, &table(root, days, answers))?;
+    fs::write(&path, readme).map_err(|e| format!("Couldn't write '{}': {}", path.display(), e))
+}
+
+#[test]
+fn test_replace_table() {
+    let readme = "Title\n\nDay | Part 1\n----|-------\n1   | x\n\nMore\n";
+    assert_eq!(replace_table(readme, "Day | new\n1 | y").unwrap(),
+               "Title\n\nDay | new\n1 | y\n\nMore\n");
+    assert!(replace_table("no table here\n", "Day |").is_err());
+}
@@ -0,0 +1,100 @@
+// `aoc run` solves days and prints the answers, either for people or as one
+// JSON record per line for scripts
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha256;
+use serde_json;
+
+use days::Day;
+use run::{run, format_duration};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed_ms: f64,
+    // sha256 of the input text, so results from different inputs can be told apart
+    pub input_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn input_hash(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.input_str(text);
+    hasher.result_str()
+}
+
+// Solve both parts of a day. A parse failure gives an error record for each part.
+pub fn records(day: &Day, text: &str) -> Vec<Record> {
+    let hash = input_hash(text);
+    match run(&*day.solver, text) {
+        Ok(r) => r.parts.into_iter().zip(1..).map(|(p, part)| {
+            let elapsed_ms = p.elapsed.as_secs_f64() * 1000.0;
+            let (answer, error) = match p.answer {
+                Ok(a) => (Some(a), None),
+                Err(e) => (None, Some(e)),
+            };
+            Record { day: day.number, part, answer, elapsed_ms, input_hash: hash.clone(), error }
+        }).collect(),
+        Err(e) => (1..3).map(|part| Record {
+            day: day.number,
+            part,
+            answer: None,
+            elapsed_ms: 0.0,
+            input_hash: hash.clone(),
+            error: Some(e.clone()),
+        }).collect(),
+    }
+}
+
+pub fn print(record: &Record, format: Format) {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string(record).unwrap()),
+        Format::Text => match record.answer {
+            Some(ref a) => {
+                let elapsed = Duration::from_secs_f64(record.elapsed_ms / 1000.0);
+                println!("day {} part {}: {} ({})", record.day, record.part, a, format_duration(elapsed));
+            }
+            None => println!("day {} part {}: FAILED {}", record.day, record.part,
+                             record.error.as_deref().unwrap_or("")),
+        },
+    }
+}
+
+#[test]
+fn test_json() {
+    let record = Record {
+        day: 1,
+        part: 2,
+        answer: Some("140".to_string()),
+        elapsed_ms: 0.5,
+        input_hash: input_hash("abc"),
+        error: None,
+    };
+    assert_eq!(serde_json::to_string(&record).unwrap(),
+               "{\"day\":1,\"part\":2,\"answer\":\"140\",\"elapsed_ms\":0.5,\
+                \"input_hash\":\"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad\"}");
+}
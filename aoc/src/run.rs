@@ -0,0 +1,79 @@
+// Timed runs of a solver over one input
+
+use std::panic;
+use std::time::{Duration, Instant};
+
+use solution::Solver;
+
+pub struct Part {
+    // Err if the solver panicked, e.g. a scaffolded day that isn't done yet, or
+    // found no answer
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+pub struct Run {
+    pub parse_time: Duration,
+    // part 1 then part 2
+    pub parts: Vec<Part>,
+}
+
+impl Run {
+    pub fn total_time(&self) -> Duration {
+        self.parts.iter().fold(self.parse_time, |total, p| total + p.elapsed)
+    }
+}
+
+pub fn panic_message(e: Box<dyn std::any::Any + Send>) -> String {
+    match e.downcast::<String>() {
+        Ok(s) => *s,
+        Err(e) => match e.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+// Parse `text` and solve both parts, timing each step. Only a parse error
+// stops the run.
+pub fn run(solver: &dyn Solver, text: &str) -> Result<Run, String> {
+    let start = Instant::now();
+    let input = solver.parse(text)?;
+    let parse_time = start.elapsed();
+
+    let parts = (1..3).map(|part| {
+        let start = Instant::now();
+        let answer = panic::catch_unwind(panic::AssertUnwindSafe(|| solver.solve(&*input, part)))
+                         .map_err(panic_message)
+                         .and_then(|answer| answer);
+        Part { answer, elapsed: start.elapsed() }
+    }).collect();
+    Ok(Run { parse_time, parts })
+}
+
+// Short human readable durations
+pub fn format_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+    match micros {
+        0..=999 => format!("{} µs", micros),
+        1_000..=999_999 => format!("{} ms", micros / 1_000),
+        _ => format!("{:.1} s", d.as_secs_f64()),
+    }
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_micros(12)), "12 µs");
+    assert_eq!(format_duration(Duration::from_micros(12_345)), "12 ms");
+    assert_eq!(format_duration(Duration::from_millis(2_345)), "2.3 s");
+}
+
+#[test]
+fn test_run() {
+    let day1 = ::days::all().into_iter().next().unwrap();
+    let r = run(&*day1.solver, "R8, R4").unwrap();
+    assert_eq!(r.parts[0].answer, Ok("12".to_string()));
+    // nowhere is visited twice, so there's nothing to submit for part 2
+    assert!(r.parts[1].answer.is_err());
+    assert!(run(&*day1.solver, "X1").is_err());
+}
@@ -0,0 +1,238 @@
+// `aoc new <day>` creates a crate for the day laid out like the others and
+// wires it into the runner
+
+use std::fs;
+use std::io::prelude::*;
+use std::path::Path;
+
+const CARGO_TOML: &str = r#"[package]
+name = "aoc2016-{day}"
+version = "0.1.0"
+authors = ["Stuart Donnan <donnan.stuart@gmail.com>"]
+
+[dependencies]
+"#;
+
+const LIB_RS: &str = r#"// Convert a string into a vector of lines
+pub fn parse(text: &str) -> Result<Vec<String>, String> {
+    Result::Ok(text.lines().map(|l| l.to_string()).collect())
+}
+
+// Panic until the parts are solved, so the runner reports them as failures
+// rather than taking a placeholder for an answer
+pub fn part1(_lines: &[String]) -> String {
+    panic!("part 1 isn't solved yet")
+}
+
+pub fn part2(_lines: &[String]) -> String {
+    panic!("part 2 isn't solved yet")
+}
+"#;
+
+const MAIN_RS: &str = r#"extern crate aoc2016_{day};
+
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::process;
+
+use aoc2016_{day}::{parse, part1, part2};
+
+fn main() {
+    // get the file contents as an Option
+    let option = match env::args().count() {
+        // correct number of args? try to read the file
+        2 => {
+            let fname = env::args().nth(1).unwrap();
+            match File::open(&fname) {
+                // create string with file contents
+                Ok(mut x) => {
+                    let mut s = String::new();
+                    if x.read_to_string(&mut s).is_ok() {
+                        Option::Some(s)
+                    }
+                    else {
+                        println!("Error reading file '{}'",&fname);
+                        Option::None
+                    }
+                }
+                Err(e) => {
+                    println!("Couldn't open file '{}': {}",&fname,e);
+                    Option::None
+                }
+            }
+        }
+        // otherwise print usage
+        _ => {
+            let name = env::args().next().unwrap();
+            println!("Usage: {} input-filename", &name);
+            Option::None
+        }
+    };
+
+    // if there is a string, parse it
+    if let Some(s) = option {
+        let lines = parse(&s).unwrap();
+        println!("Part 1: {}", part1(&lines));
+        println!("Part 2: {}", part2(&lines));
+    } else {
+        process::exit(1);
+    }
+}
+"#;
+
+const EXAMPLES_TOML: &str = r#"# Worked examples from description.txt, run as tests by the aoc crate
+
+# [[example]]
+# input = ""
+# part1 = ""
+# part2 = ""
+"#;
+
+const ADAPTER_RS: &str = r#"use aoc2016_{day}::{parse, part1, part2};
+
+use solution::Solution;
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+
+    fn parse(&self, text: &str) -> Result<Vec<String>, String> {
+        parse(text)
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<String, String> {
+        Ok(part1(lines))
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<String, String> {
+        Ok(part2(lines))
+    }
+}
+"#;
+
+fn fill(template: &str, day: u32) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+// Insert `line` amongst the lines `day_of` recognises, keeping them in day
+// order. Fails if the day is already there or there's nothing to insert next to.
+fn insert_line<F>(text: &str, line: &str, day: u32, day_of: F) -> Result<String, String>
+    where F: Fn(&str) -> Option<u32>
+{
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let found = lines.iter().enumerate()
+                     .filter_map(|(i, l)| day_of(l).map(|d| (i, d)))
+                     .collect::<Vec<_>>();
+    if found.iter().any(|&(_, d)| d == day) {
+        return Err(format!("Day {} is already there", day));
+    }
+    let index = match (found.iter().rev().find(|&&(_, d)| d < day), found.first()) {
+        (Some(&(i, _)), _) => i + 1,
+        (None, Some(&(i, _))) => i,
+        (None, None) => return Err(format!("Couldn't find where to add '{}'", line.trim())),
+    };
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn between<'a>(line: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    line.trim().strip_prefix(prefix).and_then(|l| l.split(suffix).next())
+}
+
+fn register_module(text: &str, day: u32) -> Result<String, String> {
+    let text = insert_line(text, &format!("mod day{};", day), day,
+                           |l| between(l, "mod day", ";")?.parse().ok())?;
+    insert_line(&text,
+                &format!("        Day {{ number: {0}, dir: \"aoc2016-{0}\", solver: Box::new(day{0}::Day{0}) }},", day),
+                day, |l| between(l, "Day { number: ", ",")?.parse().ok())
+}
+
+fn register_dependency(text: &str, day: u32) -> Result<String, String> {
+    insert_line(text, &format!("aoc2016-{0} = {{ path = \"../aoc2016-{0}\" }}", day), day,
+                |l| between(l, "aoc2016-", " ")?.parse().ok())
+}
+
+fn register_crate(text: &str, day: u32) -> Result<String, String> {
+    insert_line(text, &format!("extern crate aoc2016_{};", day), day,
+                |l| between(l, "extern crate aoc2016_", ";")?.parse().ok())
+}
+
+fn add_readme_row(text: &str, day: u32) -> Result<String, String> {
+    insert_line(text, &format!("{:<4}|        |        |         |", day), day,
+                |l| if l.contains('|') { l.split('|').next()?.trim().parse().ok() } else { None })
+}
+
+fn update<F>(path: &Path, edit: F) -> Result<(), String>
+    where F: Fn(&str) -> Result<String, String>
+{
+    let text = fs::read_to_string(path)
+                   .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?;
+    let text = edit(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    fs::write(path, text).map_err(|e| format!("Couldn't write '{}': {}", path.display(), e))
+}
+
+fn create(path: &Path, text: &str) -> Result<(), String> {
+    fs::OpenOptions::new().write(true).create_new(true).open(path)
+                          .and_then(|mut f| f.write_all(text.as_bytes()))
+                          .map_err(|e| format!("Couldn't create '{}': {}", path.display(), e))
+}
+
+pub fn new_day(root: &Path, day: u32) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {}", day));
+    }
+    let dir = root.join(format!("aoc2016-{}", day));
+    if dir.exists() {
+        return Err(format!("'{}' already exists", dir.display()));
+    }
+    let runner = root.join("aoc");
+    let edits = [
+        (runner.join("src/days/mod.rs"), register_module as fn(&str, u32) -> Result<String, String>),
+        (runner.join("Cargo.toml"), register_dependency),
+        (runner.join("src/main.rs"), register_crate),
+        (root.join("README.md"), add_readme_row),
+    ];
+
+    // check everything can take the new day before touching anything
+    for &(ref path, edit) in &edits {
+        let text = fs::read_to_string(path)
+                       .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?;
+        edit(&text, day).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("Couldn't create '{}': {}", dir.display(), e))?;
+    create(&dir.join("Cargo.toml"), &fill(CARGO_TOML, day))?;
+    create(&dir.join(".gitignore"), "target\n")?;
+    create(&dir.join("description.txt"), "")?;
+    create(&dir.join("examples.toml"), EXAMPLES_TOML)?;
+    create(&dir.join("src/lib.rs"), LIB_RS)?;
+    create(&dir.join("src/main.rs"), &fill(MAIN_RS, day))?;
+    create(&runner.join(format!("src/days/day{}.rs", day)), &fill(ADAPTER_RS, day))?;
+
+    for &(ref path, edit) in &edits {
+        update(path, |t| edit(t, day))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_register() {
+    let module = "use solution::Solver;\n\nmod day1;\nmod day3;\n\n\
+                  pub fn all() -> Vec<Day> {\n    vec![\n\
+                  \x20       Day { number: 1, dir: \"aoc2016-1\", solver: Box::new(day1::Day1) },\n\
+                  \x20       Day { number: 3, dir: \"aoc2016-3\", solver: Box::new(day3::Day3) },\n\
+                  \x20   ]\n}\n";
+    let registered = register_module(module, 2).unwrap();
+    assert!(registered.contains("mod day1;\nmod day2;\nmod day3;\n"));
+    assert!(registered.contains("Box::new(day1::Day1) },\n        Day { number: 2, dir: \"aoc2016-2\", solver: Box::new(day2::Day2) },\n"));
+    assert!(register_module(&registered, 2).is_err());
+
+    let readme = "Day | Part 1 | Part 2 | Comments\n----|--------|--------|---------\n\
+                  1   |   ✓    |   ✓    |\n\nMore text\n";
+    assert_eq!(add_readme_row(readme, 12).unwrap(),
+               readme.replace("✓    |\n", "✓    |\n12  |        |        |         |\n"));
+    assert!(register_crate("fn main() {}\n", 2).is_err());
+}
@@ -0,0 +1,78 @@
+use std::any::Any;
+
+// One day's puzzle. `parse` turns the input file into the day's model and both
+// parts work from that. A part gives Err when the input has no answer, so it's
+// reported as a failure instead of being taken for one.
+pub trait Solution {
+    type Input: 'static;
+
+    fn parse(&self, text: &str) -> Result<Self::Input, String>;
+    fn part1(&self, input: &Self::Input) -> Result<String, String>;
+    fn part2(&self, input: &Self::Input) -> Result<String, String>;
+
+    // Solve a part while passing a line on how it's going to `report` every so
+    // often. Only days with a long search need to override this.
+    fn solve_with_progress(&self, input: &Self::Input, part: u8, _report: &mut dyn FnMut(String)) -> Result<String, String> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => panic!("there is no part {}", part),
+        }
+    }
+
+    // Shown in the comments column of the README
+    fn comments(&self) -> &'static str { "" }
+
+    // The input written back in puzzle syntax as pieces that can be dropped
+    // independently, joined with `separator`. `aoc minimise` shrinks inputs by
+    // removing pieces, or lines of the original text if this is None.
+    fn pieces(&self, _input: &Self::Input) -> Option<Vec<String>> { None }
+    fn separator(&self) -> &'static str { "\n" }
+}
+
+// Every day has a different Input type, so the registry holds them through
+// this object safe version of Solution instead. Solvers are shared between
+// threads when running several inputs or days at once.
+pub trait Solver: Send + Sync {
+    fn parse(&self, text: &str) -> Result<Box<dyn Any>, String>;
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<String, String>;
+    fn solve_with_progress(&self, input: &dyn Any, part: u8, report: &mut dyn FnMut(String)) -> Result<String, String>;
+    fn comments(&self) -> &'static str;
+    fn pieces(&self, input: &dyn Any) -> Option<Vec<String>>;
+    fn separator(&self) -> &'static str;
+}
+
+fn downcast<T: Solution>(input: &dyn Any) -> &T::Input {
+    input.downcast_ref::<T::Input>().expect("input was parsed by a different day")
+}
+
+impl<T: Solution + Send + Sync> Solver for T {
+    fn parse(&self, text: &str) -> Result<Box<dyn Any>, String> {
+        Solution::parse(self, text).map(|input| Box::new(input) as Box<dyn Any>)
+    }
+
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<String, String> {
+        match part {
+            1 => self.part1(downcast::<T>(input)),
+            2 => self.part2(downcast::<T>(input)),
+            _ => panic!("there is no part {}", part),
+        }
+    }
+
+    fn solve_with_progress(&self, input: &dyn Any, part: u8, report: &mut dyn FnMut(String)) -> Result<String, String> {
+        Solution::solve_with_progress(self, downcast::<T>(input), part, report)
+    }
+
+    fn comments(&self) -> &'static str {
+        Solution::comments(self)
+    }
+
+    fn pieces(&self, input: &dyn Any) -> Option<Vec<String>> {
+        Solution::pieces(self, downcast::<T>(input))
+    }
+
+    fn separator(&self) -> &'static str {
+        Solution::separator(self)
+    }
+}
+
@@ -0,0 +1,220 @@
+// `aoc submit --day N --part P` sends a solver's answer to the site. Every
+// attempt is logged to submissions.jsonl in the repo root, one JSON record per
+// line, and the log is checked first so we never resend an answer that was
+// already rejected, guess outside a known too high/too low bound, or submit
+// while the server still wants us to wait.
+
+use std::fs::{self, OpenOptions};
+use std::io::prelude::*;
+use std::path::Path;
+
+use serde_json;
+
+use fetch::{html_to_text, Client, Http};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // answered too recently, so the answer wasn't checked
+    TooSoon,
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    fn is_wrong(self) -> bool {
+        self == Verdict::Wrong || self == Verdict::TooHigh || self == Verdict::TooLow
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    // unix time of the submission
+    pub at: u64,
+    pub verdict: Verdict,
+    // unix time before which we shouldn't submit again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<Attempt>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(path).map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?;
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|e| format!("Bad line in '{}': {}", path.display(), e)))
+        .collect()
+}
+
+fn append_history(path: &Path, attempt: &Attempt) -> Result<(), String> {
+    let line = serde_json::to_string(attempt).unwrap();
+    OpenOptions::new().create(true).append(true).open(path)
+        .and_then(|mut f| writeln!(f, "{}", line))
+        .map_err(|e| format!("Couldn't write '{}': {}", path.display(), e))
+}
+
+// Refuse answers the history says can't be right, or if we're in a cooldown
+pub fn check(history: &[Attempt], day: u32, part: u8, answer: &str, now: u64) -> Result<(), String> {
+    if answer.trim().is_empty() {
+        return Err("Refusing to submit an empty answer".to_string());
+    }
+    if let Some(until) = history.iter().filter_map(|a| a.wait_until).max().filter(|&t| t > now) {
+        return Err(format!("The server asked us to wait, try again in {}s", until - now));
+    }
+    let number = answer.parse::<i64>().ok();
+    for a in history.iter().filter(|a| a.day == day && a.part == part) {
+        let bound = a.answer.parse::<i64>().ok();
+        match a.verdict {
+            Verdict::Right | Verdict::AlreadySolved =>
+                return Err(format!("Day {} part {} is already solved", day, part)),
+            v if v.is_wrong() && a.answer == answer =>
+                return Err(format!("{} was already rejected", answer)),
+            Verdict::TooHigh if number.is_some() && number >= bound =>
+                return Err(format!("{} can't be right, {} was too high", answer, a.answer)),
+            Verdict::TooLow if number.is_some() && number <= bound =>
+                return Err(format!("{} can't be right, {} was too low", answer, a.answer)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+pub fn verdict(text: &str) -> Verdict {
+    if text.contains("That's the right answer") {
+        Verdict::Right
+    } else if text.contains("too high") {
+        Verdict::TooHigh
+    } else if text.contains("too low") {
+        Verdict::TooLow
+    } else if text.contains("not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("answer too recently") {
+        Verdict::TooSoon
+    } else if text.contains("already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown
+    }
+}
+
+// Seconds to wait, from "You have 1m 32s left to wait" or "Please wait one
+// minute before trying again"
+pub fn wait(text: &str) -> Option<u64> {
+    let text = text.replace('\n', " ");
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].rfind("have ").map_or(0, |i| i + 5);
+        return text[start..end].split_whitespace().map(|t| {
+            let (n, unit) = t.split_at(t.len() - 1);
+            n.parse::<u64>().ok().map(|n| match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                _ => n,
+            })
+        }).sum();
+    }
+    let start = text.find("wait ")? + 5;
+    let mut words = text[start..].split_whitespace();
+    let n = match words.next()? {
+        "one" => 1, "two" => 2, "three" => 3, "four" => 4, "five" => 5,
+        "six" => 6, "seven" => 7, "eight" => 8, "nine" => 9, "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    match words.next()?.trim_end_matches('.') {
+        "second" | "seconds" => Some(n),
+        "minute" | "minutes" => Some(n * 60),
+        _ => None,
+    }
+}
+
+// Check the answer against the history, send it and log the result. Returns
+// the attempt and the server's message.
+pub fn submit<H: Http>(client: &Client<H>, history: &Path, day: u32, part: u8, answer: &str,
+                       now: u64) -> Result<(Attempt, String), String> {
+    check(&load_history(history)?, day, part, answer, now)?;
+    let body = client.post(&format!("/2016/day/{}/answer", day),
+                           &[("level", &part.to_string()), ("answer", answer)])?;
+    let message = html_to_text(&body);
+    let attempt = Attempt {
+        day,
+        part,
+        answer: answer.to_string(),
+        at: now,
+        verdict: verdict(&message),
+        wait_until: wait(&message).map(|w| now + w),
+    };
+    append_history(history, &attempt)?;
+    Ok((attempt, message))
+}
+
+#[test]
+fn test_check() {
+    let attempt = |part, answer: &str, verdict| Attempt {
+        day: 1, part, answer: answer.to_string(), at: 0, verdict, wait_until: Some(60),
+    };
+    let history = vec![
+        attempt(1, "100", Verdict::TooHigh),
+        attempt(1, "10", Verdict::TooLow),
+        attempt(1, "abc", Verdict::Wrong),
+        attempt(2, "7", Verdict::Right),
+    ];
+    assert!(check(&history, 1, 1, "50", 30).unwrap_err().contains("wait"));
+    assert_eq!(check(&history, 1, 1, "50", 60), Ok(()));
+    assert!(check(&history, 1, 1, "100", 60).is_err());
+    assert!(check(&history, 1, 1, "150", 60).is_err());
+    assert!(check(&history, 1, 1, "9", 60).is_err());
+    assert!(check(&history, 1, 1, "abc", 60).is_err());
+    assert_eq!(check(&history, 1, 1, "xyz", 60), Ok(()));
+    assert!(check(&history, 1, 2, "8", 60).is_err());
+    assert_eq!(check(&history, 2, 2, "8", 60), Ok(()));
+}
+
+#[test]
+fn test_response() {
+    let wrong = "That's not the right answer; your answer is too low. If you're stuck, \
+                 make sure you're using the full input data. Please wait one minute \
+                 before trying again.";
+    assert_eq!((verdict(wrong), wait(wrong)), (Verdict::TooLow, Some(60)));
+    let soon = "You gave an answer too recently; you have to wait after submitting an \
+                answer before trying again. You have 1m 32s left to wait.";
+    assert_eq!((verdict(soon), wait(soon)), (Verdict::TooSoon, Some(92)));
+    assert_eq!(wait("Please wait 5 minutes before trying again."), Some(300));
+    assert_eq!((verdict("That's the right answer!"), wait("That's the right answer!")),
+               (Verdict::Right, None));
+}
+
+#[test]
+fn test_submit() {
+    use std::time::Duration;
+    use fetch::{stub_server, Ureq};
+
+    let dir = ::std::env::temp_dir().join(format!("aoc-submit-{}", ::std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let history = dir.join("submissions.jsonl");
+    let (url, server) = stub_server(vec![
+        (200, "<main><article><p>That's not the right answer; your answer is too high. \
+               Please wait one minute before trying again.</p></article></main>"),
+    ]);
+    let mut client = Client::new(Ureq::new(), &url, "abc", &dir.join("stamp"));
+    client.interval = Duration::from_millis(0);
+
+    let (attempt, _) = submit(&client, &history, 3, 1, "900", 1000).unwrap();
+    assert_eq!((attempt.verdict, attempt.wait_until), (Verdict::TooHigh, Some(1060)));
+    assert_eq!(load_history(&history), Ok(vec![attempt]));
+    // refused without asking the server, which would fail as the stub has stopped
+    assert!(submit(&client, &history, 3, 1, "800", 1010).unwrap_err().contains("wait"));
+    assert!(submit(&client, &history, 3, 1, "950", 2000).unwrap_err().contains("too high"));
+
+    assert_eq!(server.join().unwrap(), vec![
+        "POST /2016/day/3/answer HTTP/1.1 Cookie: session=abc level=1&answer=900",
+    ]);
+    fs::remove_dir_all(&dir).unwrap();
+}
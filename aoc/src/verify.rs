@@ -0,0 +1,121 @@
+// Run every solver against its worked examples and recorded inputs and check
+// the answers haven't changed
+
+use std::any::Any;
+use std::fs::File;
+use std::io::prelude::*;
+use std::panic;
+use std::path::Path;
+
+use answers::{Answers, Parts};
+use days::Day;
+use examples;
+use run::panic_message;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    let mut text = String::new();
+    File::open(path).and_then(|mut f| f.read_to_string(&mut text))
+                    .map_err(|e| format!("Couldn't read '{}': {}", path.display(), e))?;
+    Ok(text)
+}
+
+// Parse one input and compare each part that has an expected answer. A solver
+// that panics fails that check rather than stopping the others.
+fn check(day: &Day, name: &str, text: Result<String, String>, expected: &Parts,
+         summary: &mut Summary) {
+    let parsed = text.and_then(|t| {
+        panic::catch_unwind(panic::AssertUnwindSafe(|| day.solver.parse(&t))).map_err(panic_message)?
+    });
+    let parsed: Box<dyn Any> = match parsed {
+        Ok(p) => p,
+        Err(e) => {
+            println!("day {} ({}) ... FAILED\n    {}", day.number, name, e);
+            summary.failed += 1;
+            return;
+        }
+    };
+    for part in 1..3 {
+        let expected = match expected.get(part) {
+            Some(e) => e,
+            None => continue,
+        };
+        let got = panic::catch_unwind(panic::AssertUnwindSafe(|| day.solver.solve(&*parsed, part)))
+                      .map_err(panic_message);
+        match got.and_then(|got| got) {
+            Ok(ref got) if got == expected => {
+                println!("day {} part {} ({}) ... ok", day.number, part, name);
+                summary.passed += 1;
+            }
+            Ok(got) => {
+                println!("day {} part {} ({}) ... FAILED", day.number, part, name);
+                println!("    expected: {}", expected);
+                println!("         got: {}", got);
+                summary.failed += 1;
+            }
+            Err(e) => {
+                println!("day {} part {} ({}) ... FAILED\n    {}", day.number, part, name, e);
+                summary.failed += 1;
+            }
+        }
+    }
+}
+
+// Check a day against the worked examples in its examples.toml
+pub fn verify_examples(root: &Path, day: &Day, summary: &mut Summary) {
+    match examples::load(&root.join(day.dir).join("examples.toml")) {
+        Ok(examples) => for (index, example) in examples.into_iter().enumerate() {
+            let name = format!("example {}", index + 1);
+            check(day, &name, Ok(example.input), &example.expected, summary);
+        },
+        Err(e) => {
+            println!("day {} (examples) ... FAILED\n    {}", day.number, e);
+            summary.failed += 1;
+        }
+    }
+}
+
+pub fn verify(root: &Path, days: &[Day], answers: &Answers) -> Summary {
+    let mut summary = Summary::default();
+    for day in days {
+        let dir = root.join(day.dir);
+        verify_examples(root, day, &mut summary);
+
+        let inputs = answers.for_day(day.number);
+        if inputs.is_empty() {
+            println!("day {}: no recorded answers", day.number);
+        }
+        for (name, parts) in inputs {
+            check(day, name, read_input(&dir.join(name)), parts, &mut summary);
+        }
+    }
+    summary
+}
+
+#[test]
+fn test_check_panics() {
+    use solution::Solution;
+
+    // a day whose part 1 panics on anything but an empty input
+    struct Touchy;
+    impl Solution for Touchy {
+        type Input = String;
+        fn parse(&self, text: &str) -> Result<String, String> { Ok(text.to_string()) }
+        fn part1(&self, text: &String) -> Result<String, String> {
+            assert!(text.is_empty(), "not empty");
+            Ok(String::new())
+        }
+        fn part2(&self, text: &String) -> Result<String, String> { Ok(text.len().to_string()) }
+    }
+
+    let day = Day { number: 0, dir: "", solver: Box::new(Touchy) };
+    let expected = Parts { part1: Some(String::new()), part2: Some("3".to_string()) };
+    let mut summary = Summary::default();
+    check(&day, "test", Ok("abc".to_string()), &expected, &mut summary);
+    assert_eq!(summary, Summary { passed: 1, failed: 1 });
+}
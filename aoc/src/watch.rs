@@ -0,0 +1,143 @@
+// `aoc watch --day N` re-runs a day's examples and both parts whenever its
+// source, input.txt, examples.toml or answers change. The day's code is built
+// into this binary, so each run is a fresh `cargo run -- watch --day N --once`
+// which rebuilds first.
+
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{self, EventKind, RecursiveMode, Watcher};
+
+use answers::Answers;
+use days::Day;
+use run::{run, format_duration};
+use verify::{self, Summary};
+
+// One pass over the day: the worked examples, then the input with each part
+// marked against answers.toml
+pub fn once(root: &Path, day: &Day, answers: &Answers) -> Summary {
+    let mut summary = Summary::default();
+    verify::verify_examples(root, day, &mut summary);
+
+    let path = root.join(day.dir).join("input.txt");
+    let text = match fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(e) => {
+            println!("day {}: couldn't read '{}': {}", day.number, path.display(), e);
+            return summary;
+        }
+    };
+    let result = match run(&*day.solver, &text) {
+        Ok(r) => r,
+        Err(e) => {
+            println!("day {} parse (input.txt) ... FAILED\n    {}", day.number, e);
+            summary.failed += 1;
+            return summary;
+        }
+    };
+    println!("day {} parse (input.txt) ... ok ({})", day.number, format_duration(result.parse_time));
+    let expected = answers.for_day(day.number).into_iter()
+                          .find(|&(name, _)| name == "input.txt")
+                          .map(|(_, parts)| parts);
+    for (p, part) in result.parts.iter().zip(1..) {
+        match (&p.answer, expected.and_then(|e| e.get(part))) {
+            (Ok(a), Some(e)) if a == e => {
+                println!("day {} part {} (input.txt) ... {} ✓ ({})",
+                         day.number, part, a, format_duration(p.elapsed));
+                summary.passed += 1;
+            }
+            (Ok(a), Some(e)) => {
+                println!("day {} part {} (input.txt) ... {} ✗ expected {} ({})",
+                         day.number, part, a, e, format_duration(p.elapsed));
+                summary.failed += 1;
+            }
+            (Ok(a), None) => println!("day {} part {} (input.txt) ... {} ({})",
+                                      day.number, part, a, format_duration(p.elapsed)),
+            (Err(e), _) => {
+                println!("day {} part {} (input.txt) ... FAILED\n    {}", day.number, part, e);
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
+
+// Whether a change to `path` should trigger a re-run
+fn relevant(root: &Path, day: &Day, path: &Path) -> bool {
+    if path.components().any(|c| c.as_os_str() == "target") {
+        return false;
+    }
+    let day_dir = root.join(day.dir);
+    (path.starts_with(&day_dir) && !path.ends_with("Cargo.lock")) ||
+        path == root.join("answers.toml") ||
+        path == root.join("aoc/src/days").join(format!("day{}.rs", day.number))
+}
+
+fn rerun(root: &Path, day: &Day) {
+    if io::stdout().is_terminal() {
+        print!("\x1b[2J\x1b[H");
+    }
+    println!("== day {} ==", day.number);
+    let mut cargo = Command::new("cargo");
+    cargo.args(["run", "--quiet", "--manifest-path"])
+         .arg(root.join("aoc/Cargo.toml"));
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    cargo.args(["--", "watch", "--day", &day.number.to_string(), "--once"]);
+    match cargo.status() {
+        Ok(status) if status.success() => println!("all passed"),
+        Ok(_) => println!("FAILED"),
+        Err(e) => println!("Couldn't run cargo: {}", e),
+    }
+    println!("watching for changes...");
+}
+
+pub fn watch(root: &Path, day: &Day) -> Result<(), String> {
+    // events come with canonical paths, so compare against a canonical root
+    let root = &root.canonicalize().map_err(|e| e.to_string())?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    for &(ref path, mode) in &[
+        (root.join(day.dir), RecursiveMode::Recursive),
+        (root.join("aoc/src/days"), RecursiveMode::NonRecursive),
+        (root.to_path_buf(), RecursiveMode::NonRecursive),
+    ] {
+        watcher.watch(path, mode)
+               .map_err(|e| format!("Couldn't watch '{}': {}", path.display(), e))?;
+    }
+
+    rerun(root, day);
+    for event in &rx {
+        let event = event.map_err(|e| e.to_string())?;
+        let changed = match event.kind {
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
+                event.paths.iter().any(|p| relevant(root, day, p))
+            }
+            _ => false,
+        };
+        if changed {
+            // editors tend to write several times in a row, so let it settle
+            while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+            rerun(root, day);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_relevant() {
+    let root = Path::new("/repo");
+    let day = ::days::all().remove(1);
+    assert!(relevant(root, &day, Path::new("/repo/aoc2016-2/src/lib.rs")));
+    assert!(relevant(root, &day, Path::new("/repo/aoc2016-2/input.txt")));
+    assert!(relevant(root, &day, Path::new("/repo/aoc/src/days/day2.rs")));
+    assert!(relevant(root, &day, Path::new("/repo/answers.toml")));
+    assert!(!relevant(root, &day, Path::new("/repo/aoc2016-2/target/debug/foo")));
+    assert!(!relevant(root, &day, Path::new("/repo/aoc2016-3/src/lib.rs")));
+    assert!(!relevant(root, &day, Path::new("/repo/README.md")));
+}
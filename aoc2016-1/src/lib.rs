@@ -0,0 +1,551 @@
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    Left,
+    Right,
+    // the rest are only in the extended grammar
+    // keep facing the same way
+    Forward,
+    // keep facing the same way but walk backwards
+    Back,
+    // turn around
+    UTurn
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Step {
+    pub dir  : Direction,
+    pub dist : isize
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Direction::Left    => write!(f, "L"),
+            Direction::Right   => write!(f, "R"),
+            Direction::Forward => write!(f, "F"),
+            Direction::Back    => write!(f, "B"),
+            Direction::UTurn   => write!(f, "U"),
+        }
+    }
+}
+
+impl Step {
+    // How far the step walks along the way it faces after turning, which is
+    // negative when walking backwards
+    pub fn offset(&self) -> isize {
+        if self.dir == Direction::Back { -self.dist } else { self.dist }
+    }
+}
+
+// In puzzle syntax, e.g. "R2"
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.dir, self.dist)
+    }
+}
+
+// Which instructions parse_with accepts
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Grammar {
+    // just L and R, as in the puzzle
+    Puzzle,
+    // F, B and U as well
+    Extended
+}
+
+// Convert a string of puzzle instructions into a vector of steps
+pub fn parse(text: &str) -> Result<Vec<Step>, String> {
+    parse_with(text, Grammar::Puzzle)
+}
+
+pub fn parse_with(text: &str, grammar: Grammar) -> Result<Vec<Step>, String> {
+    let mut vec: Vec<Step> = Vec::new();
+    for entry in text.split(',') {
+        let e: &str = entry.trim();
+        if e.len() <= 1 {
+            return Result::Err(format!("Invalid input at '{}'",entry));
+        }
+        let mut chars = e.chars();
+        let dir = match (chars.next().unwrap(), grammar) {
+            ('L', _) => Direction::Left,
+            ('R', _) => Direction::Right,
+            ('F', Grammar::Extended) => Direction::Forward,
+            ('B', Grammar::Extended) => Direction::Back,
+            ('U', Grammar::Extended) => Direction::UTurn,
+            _   => {return Result::Err(format!("Invalid direction at '{}'",entry))}
+        };
+        // only plain digits, so that e.g. "L+3" isn't read back as "L3"
+        let digits = chars.as_str();
+        let dist = match digits.parse::<isize>() {
+            Ok(i) if digits.bytes().all(|b| b.is_ascii_digit()) => i,
+            Ok(i) if i < 0 => return Result::Err(format!("Negative distance at '{}'",entry)),
+            _ => return Result::Err(format!("Invalid distance at '{}'",entry)),
+        };
+        vec.push(Step {dir: dir,dist: dist});
+    }
+    Result::Ok(vec)
+}
+
+// The reverse of parse, e.g. "R2, L3"
+pub fn render(steps: &[Step]) -> String {
+    steps.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Coordinate {
+    pub x : isize,
+    pub y : isize
+}
+
+impl Coordinate {
+    // Manhattan distance from the origin
+    pub fn distance(&self) -> isize { self.x.abs() + self.y.abs() }
+
+    // `dist` blocks away facing `facing`
+    pub fn moved(&self, facing: AbsDir, dist: isize) -> Coordinate {
+        match facing {
+            AbsDir::North => Coordinate { x: self.x, y: self.y + dist },
+            AbsDir::South => Coordinate { x: self.x, y: self.y - dist },
+            AbsDir::East  => Coordinate { x: self.x + dist, y: self.y },
+            AbsDir::West  => Coordinate { x: self.x - dist, y: self.y },
+        }
+    }
+}
+
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("({}, {})", self.x, self.y))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AbsDir { North, East, South, West }
+
+impl fmt::Display for AbsDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match *self {
+            AbsDir::North => "North",
+            AbsDir::East  => "East",
+            AbsDir::South => "South",
+            AbsDir::West  => "West",
+        })
+    }
+}
+
+// Determine the new absolute direction based on the relative update
+pub fn turn(a: AbsDir, t: Direction) -> AbsDir {
+    match t {
+        Direction::Left => match a {
+            AbsDir::North => AbsDir::West,
+            AbsDir::West  => AbsDir::South,
+            AbsDir::South => AbsDir::East,
+            AbsDir::East  => AbsDir::North
+        },
+        Direction::Right => match a {
+            AbsDir::North => AbsDir::East,
+            AbsDir::East  => AbsDir::South,
+            AbsDir::South => AbsDir::West,
+            AbsDir::West  => AbsDir::North,
+        },
+        Direction::Forward | Direction::Back => a,
+        Direction::UTurn => turn(turn(a, Direction::Left), Direction::Left),
+    }
+}
+
+// Where a walk starts and which way it faces, the puzzle's being the origin
+// facing north
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Start {
+    pub at     : Coordinate,
+    pub facing : AbsDir
+}
+
+impl Default for Start {
+    fn default() -> Start {
+        Start { at: Coordinate {x:0,y:0}, facing: AbsDir::North }
+    }
+}
+
+// calculate the final destination coordinates
+// assume the following coordinate system mapping
+// -x w
+// x  e
+// -y s
+// y  n
+pub fn calc_dest(steps: &[Step]) -> Coordinate {
+    calc_dest_from(steps, Start::default())
+}
+
+pub fn calc_dest_from(steps: &[Step], start: Start) -> Coordinate {
+    let mut facing = start.facing;
+    let mut location = start.at;
+    for step in steps {
+        facing = turn(facing, step.dir);
+        location = location.moved(facing, step.offset());
+    }
+    location
+}
+
+// The first location visited twice, if there is one
+pub fn calc_dest2(steps: &[Step]) -> Option<Coordinate> {
+    calc_dest2_from(steps, Start::default())
+}
+
+pub fn calc_dest2_from(steps: &[Step], start: Start) -> Option<Coordinate> {
+    let mut visited = HashSet::new();
+    visited.insert(start.at);
+    // leave early if we already visited this spot, otherwise we save it and
+    // keep going
+    blocks(steps, start).map(|(_, at)| at).find(|&at| !visited.insert(at))
+}
+
+// A location visited more than once, with the index of the step that walked
+// onto it each time, or None for starting there
+#[derive(Debug, PartialEq, Clone)]
+pub struct Revisit {
+    pub at    : Coordinate,
+    pub steps : Vec<Option<usize>>
+}
+
+impl Revisit {
+    pub fn visits(&self) -> usize { self.steps.len() }
+}
+
+// Every location visited more than once, in the order of their first revisit
+pub fn revisits(steps: &[Step]) -> Vec<Revisit> {
+    revisits_from(steps, Start::default())
+}
+
+pub fn revisits_from(steps: &[Step], start: Start) -> Vec<Revisit> {
+    let mut visits : HashMap<Coordinate, Vec<Option<usize>>> = HashMap::new();
+    visits.insert(start.at, vec![None]);
+    let mut order = Vec::new();
+    for (index, at) in blocks(steps, start) {
+        let by = visits.entry(at).or_default();
+        by.push(Some(index));
+        if by.len() == 2 {
+            order.push(at);
+        }
+    }
+    order.into_iter().map(|at| Revisit { at, steps: visits.remove(&at).unwrap() }).collect()
+}
+
+// One step of a walk: which way we faced before and after turning, and the
+// segment walked
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TraceStep {
+    pub index  : usize,
+    pub step   : Step,
+    pub before : AbsDir,
+    pub after  : AbsDir,
+    pub from   : Coordinate,
+    pub to     : Coordinate
+}
+
+// e.g. "   3 R4     South -> West   (8, -4) -> (4, -4)"
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>4} {:<6} {:<5} -> {:<5}  {} -> {}",
+               self.index, self.step.to_string(), self.before, self.after, self.from, self.to)
+    }
+}
+
+// Every step of the walk calc_dest takes
+pub fn trace(steps: &[Step]) -> Vec<TraceStep> {
+    trace_from(steps, Start::default())
+}
+
+pub fn trace_from(steps: &[Step], start: Start) -> Vec<TraceStep> {
+    let mut facing = start.facing;
+    let mut location = start.at;
+    steps.iter().enumerate().map(|(index, &step)| {
+        let before = facing;
+        facing = turn(facing, step.dir);
+        let from = location;
+        location = location.moved(facing, step.offset());
+        TraceStep { index, step, before, after: facing, from, to: location }
+    }).collect()
+}
+
+// Every block walked onto, with the index of the step walking onto it
+fn blocks(steps: &[Step], start: Start) -> impl Iterator<Item = (usize, Coordinate)> {
+    trace_from(steps, start).into_iter().flat_map(|t| {
+        let offset = t.step.offset();
+        (1..offset.abs() + 1).map(move |n| (t.index, t.from.moved(t.after, n * offset.signum())))
+    })
+}
+
+// Where a replay stops
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Stop {
+    // after the step with this index
+    Step(usize),
+    // on the first block walked onto with these coordinates
+    Visit(Coordinate),
+}
+
+// The trace up to where the walk stops, None if it never does. Stopping at a
+// visit cuts the last segment short at the coordinate, and stopping at the
+// start gives no steps at all.
+pub fn replay(steps: &[Step], stop: Stop) -> Option<Vec<TraceStep>> {
+    replay_from(steps, Start::default(), stop)
+}
+
+pub fn replay_from(steps: &[Step], start: Start, stop: Stop) -> Option<Vec<TraceStep>> {
+    let mut walked = trace_from(steps, start);
+    let end = match stop {
+        Stop::Step(index) => index,
+        Stop::Visit(target) if target == start.at => return Some(Vec::new()),
+        Stop::Visit(target) => {
+            let (index, at) = blocks(steps, start).find(|&(_, at)| at == target)?;
+            walked[index].to = at;
+            index
+        }
+    };
+    if end >= walked.len() {
+        return None;
+    }
+    walked.truncate(end + 1);
+    Some(walked)
+}
+
+// Which way we face after the steps
+pub fn facing(steps: &[Step]) -> AbsDir {
+    facing_from(steps, AbsDir::North)
+}
+
+pub fn facing_from(steps: &[Step], start: AbsDir) -> AbsDir {
+    steps.iter().fold(start, |facing, step| turn(facing, step.dir))
+}
+
+// The fewest steps from `from` facing `facing` to `to`, and ending up facing
+// `end` if there is one. Every step turns, so steps alternate between the two
+// axes, and four are always enough.
+pub fn route(from: Coordinate, facing: AbsDir, to: Coordinate, end: Option<AbsDir>) -> Vec<Step> {
+    for count in 0..5 {
+        for turns in 0..1usize << count {
+            let dirs = (0..count).map(|i| if turns >> i & 1 == 0 { Direction::Left } else { Direction::Right })
+                                 .collect::<Vec<_>>();
+            let headings = dirs.iter().scan(facing, |h, &d| { *h = turn(*h, d); Some(*h) }).collect::<Vec<_>>();
+            if end.is_some() && headings.last().cloned().or(Some(facing)) != end {
+                continue;
+            }
+            // the first step heading the right way along each axis walks
+            // all of it, and the rest just turn
+            let mut left = Coordinate { x: to.x - from.x, y: to.y - from.y };
+            let dists = headings.iter().map(|&h| {
+                let unit = Coordinate {x:0,y:0}.moved(h, 1);
+                let along = unit.x * left.x + unit.y * left.y;
+                if along <= 0 {
+                    return 0;
+                }
+                left = left.moved(h, -along);
+                along
+            }).collect::<Vec<_>>();
+            if left == (Coordinate {x:0,y:0}) {
+                return dirs.into_iter().zip(dists).map(|(dir, dist)| Step { dir, dist }).collect();
+            }
+        }
+    }
+    unreachable!("four steps reach anywhere facing any way")
+}
+
+// The fewest steps ending where the walk does
+pub fn simplify(steps: &[Step]) -> Vec<Step> {
+    simplify_from(steps, Start::default())
+}
+
+pub fn simplify_from(steps: &[Step], start: Start) -> Vec<Step> {
+    route(start.at, start.facing, calc_dest_from(steps, start), None)
+}
+
+// Steps ending where the walk does with the same first revisit: straight there,
+// round a square of blocks that starts and ends on it, then on to the end
+pub fn simplify_keeping_revisit(steps: &[Step]) -> Vec<Step> {
+    simplify_keeping_revisit_from(steps, Start::default())
+}
+
+pub fn simplify_keeping_revisit_from(steps: &[Step], start: Start) -> Vec<Step> {
+    let revisit = match calc_dest2_from(steps, start) {
+        Some(r) => r,
+        None => return simplify_from(steps, start),
+    };
+    let mut simple = route(start.at, start.facing, revisit, None);
+    simple.push(Step { dir: Direction::Right, dist: 1 });
+    simple.extend((0..3).map(|_| Step { dir: Direction::Left, dist: 1 }));
+    let tail = route(revisit, facing_from(&simple, start.facing), calc_dest_from(steps, start), None);
+    simple.extend(tail);
+    simple
+}
+
+// The fewest steps back to the origin facing north after the walk
+pub fn route_home(steps: &[Step]) -> Vec<Step> {
+    route_home_from(steps, Start::default())
+}
+
+// The fewest steps back to the start, facing the way the walk started
+pub fn route_home_from(steps: &[Step], start: Start) -> Vec<Step> {
+    route(calc_dest_from(steps, start), facing_from(steps, start.facing), start.at, Some(start.facing))
+}
+
+#[test]
+fn test_extended() {
+    assert_eq!(parse("R2, F3"), Err("Invalid direction at ' F3'".to_string()));
+    assert_eq!(parse("R2, L-3"), Err("Negative distance at ' L-3'".to_string()));
+    assert_eq!(parse_with("R2, L-3", Grammar::Extended), Err("Negative distance at ' L-3'".to_string()));
+    assert_eq!(parse("R2, L+3"), Err("Invalid distance at ' L+3'".to_string()));
+    assert_eq!(parse_with("F+1", Grammar::Extended), Err("Invalid distance at 'F+1'".to_string()));
+    let steps = parse_with("F2, R3, B1, U4", Grammar::Extended).unwrap();
+    assert_eq!(render(&steps), "F2, R3, B1, U4");
+    assert_eq!(calc_dest(&steps), Coordinate {x:-2,y:2});
+    // backing up onto a block R3 walked over
+    assert_eq!(calc_dest2(&steps), Some(Coordinate {x:2,y:2}));
+    let start = Start { at: Coordinate {x:10,y:10}, facing: AbsDir::South };
+    assert_eq!(calc_dest_from(&steps, start), Coordinate {x:12,y:8});
+    assert_eq!(trace_from(&steps, start)[3].before, AbsDir::West);
+}
+
+#[test]
+fn test_route() {
+    let steps = parse("R5, L5, R5, R3").unwrap();
+    assert_eq!(render(&simplify(&steps)), "R10, L2");
+    assert_eq!(render(&route_home(&steps)), "R10, L2, L0, L0");
+    assert_eq!(render(&simplify(&parse("R2, L2, L2").unwrap())), "R0, L2");
+    assert_eq!(render(&simplify_keeping_revisit(&parse("R8, R4, R4, R8").unwrap())),
+               "R4, R1, L1, L1, L1, R4");
+    let start = Start { at: Coordinate {x:10,y:10}, facing: AbsDir::South };
+    assert_eq!(render(&simplify_from(&steps, start)), "R10, L2");
+    let home = route_home_from(&steps, start);
+    let there_and_back = [steps, home].concat();
+    assert_eq!(trace_from(&there_and_back, start).last().map(|t| (t.to, t.after)),
+               Some((start.at, start.facing)));
+}
+
+#[test]
+fn test_trace() {
+    let steps = parse("R8, R4, R4, R8").unwrap();
+    let walked = trace(&steps);
+    assert_eq!(walked.last().unwrap().to, calc_dest(&steps));
+    assert_eq!(walked[2].to_string(), "   2 R4     South -> West   (8, -4) -> (4, -4)");
+
+    // the first time, not the revisit calc_dest2 finds
+    let visit = replay(&steps, Stop::Visit(calc_dest2(&steps).unwrap())).unwrap();
+    assert_eq!((visit.len(), visit[0].to), (1, Coordinate {x:4,y:0}));
+    let visit = replay(&steps, Stop::Visit(Coordinate {x:4,y:2})).unwrap();
+    assert_eq!((visit.len(), visit[3].from, visit[3].to),
+               (4, Coordinate {x:4,y:-4}, Coordinate {x:4,y:2}));
+    assert_eq!(replay(&steps, Stop::Step(1)).unwrap(), walked[..2].to_vec());
+    assert_eq!(replay(&steps, Stop::Step(4)), None);
+    assert_eq!(replay(&steps, Stop::Visit(Coordinate {x:1,y:1})), None);
+    assert_eq!(replay(&steps, Stop::Visit(Coordinate {x:0,y:0})), Some(Vec::new()));
+
+    let start = Start { at: Coordinate {x:10,y:10}, facing: AbsDir::South };
+    let walked = replay_from(&steps, start, Stop::Visit(Coordinate {x:6,y:10})).unwrap();
+    assert_eq!((walked.len(), walked[0].from), (1, Coordinate {x:10,y:10}));
+    assert_eq!(replay_from(&steps, start, Stop::Visit(start.at)), Some(Vec::new()));
+}
+
+#[test]
+fn test_revisits() {
+    let steps = parse("R8, R4, R4, R8").unwrap();
+    assert_eq!(calc_dest2(&steps), Some(Coordinate {x:4,y:0}));
+    assert_eq!(revisits(&steps),
+               vec![Revisit { at: Coordinate {x:4,y:0}, steps: vec![Some(0), Some(3)] }]);
+    let start = Start { at: Coordinate {x:10,y:10}, facing: AbsDir::South };
+    assert_eq!(revisits_from(&steps, start),
+               vec![Revisit { at: Coordinate {x:6,y:10}, steps: vec![Some(0), Some(3)] }]);
+    // walking back along a segment revisits every block, and the origin last
+    let steps = parse("R2, R0, R2").unwrap();
+    let all = revisits(&steps).into_iter().map(|r| (r.at.x, r.visits(), r.steps[0])).collect::<Vec<_>>();
+    assert_eq!(all, vec![(1, 2, Some(0)), (0, 2, None)]);
+    assert_eq!(calc_dest2(&parse("R2, L3").unwrap()), None);
+}
+
+#[cfg(test)]
+fn steps() -> impl proptest::strategy::Strategy<Value = Vec<Step>> {
+    use proptest::prelude::*;
+    let dir = prop_oneof![Just(Direction::Left), Just(Direction::Right)];
+    prop::collection::vec((dir, 0..1000isize).prop_map(|(dir, dist)| Step { dir, dist }), 1..50)
+}
+
+#[cfg(test)]
+fn extended_steps() -> impl proptest::strategy::Strategy<Value = Vec<Step>> {
+    use proptest::prelude::*;
+    let dir = prop_oneof![Just(Direction::Left), Just(Direction::Right), Just(Direction::Forward),
+                          Just(Direction::Back), Just(Direction::UTurn)];
+    prop::collection::vec((dir, 0..1000isize).prop_map(|(dir, dist)| Step { dir, dist }), 1..50)
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn parse_never_panics(text in "\\PC*") {
+        let _ = parse(&text);
+    }
+
+    #[test]
+    fn parse_round_trips(steps in steps()) {
+        prop_assert_eq!(parse(&(render(&steps) + "\n")), Ok(steps));
+    }
+
+    #[test]
+    fn extended_parse_round_trips(steps in extended_steps()) {
+        prop_assert_eq!(parse_with(&render(&steps), Grammar::Extended), Ok(steps));
+    }
+
+    #[test]
+    fn render_round_trips(text in "[LR](0|[1-9][0-9]{0,3})(, [LR](0|[1-9][0-9]{0,3})){0,49}") {
+        prop_assert_eq!(render(&parse(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn simplify_keeps_the_destination(steps in steps()) {
+        let simple = simplify(&steps);
+        prop_assert!(simple.len() <= 2);
+        prop_assert_eq!(calc_dest(&simple), calc_dest(&steps));
+        let simple = simplify_keeping_revisit(&steps);
+        prop_assert_eq!(calc_dest(&simple), calc_dest(&steps));
+        prop_assert_eq!(calc_dest2(&simple), calc_dest2(&steps));
+    }
+
+    #[test]
+    fn route_home_returns(steps in steps()) {
+        let home = route_home(&steps);
+        let there_and_back = [steps, home.clone()].concat();
+        prop_assert!(home.len() <= 4);
+        prop_assert_eq!((calc_dest(&there_and_back), facing(&there_and_back)),
+                        (Coordinate {x:0,y:0}, AbsDir::North));
+    }
+
+    #[test]
+    fn parse_rejects_bad_steps(steps in steps(), bad in "[^LR,\\s][0-9]{1,3}|[LR][0-9]*[^0-9,\\s]",
+                               at in 0..50usize) {
+        let mut entries = render(&steps).split(", ").map(String::from).collect::<Vec<_>>();
+        let at = at % entries.len();
+        entries[at] = bad.clone();
+        let err = parse(&entries.join(", ")).unwrap_err();
+        prop_assert!(err.contains(&bad), "{} doesn't mention {}", err, bad);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    extern crate serde_json;
+    let steps = parse("R2, L3").unwrap();
+    let json = serde_json::to_string(&steps).unwrap();
+    assert_eq!(json, r#"[{"dir":"Right","dist":2},{"dir":"Left","dist":3}]"#);
+    assert_eq!(serde_json::from_str::<Vec<Step>>(&json).unwrap(), steps);
+}
@@ -1,122 +1,91 @@
+extern crate aoc2016_1;
+
 use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 use std::process;
 
-#[derive(Debug, Clone, Copy)]
-enum Direction { Left, Right }
+use aoc2016_1::{parse_with, render, calc_dest_from, calc_dest2_from, replay_from, revisits_from, route_home_from,
+                simplify_from, simplify_keeping_revisit_from, trace_from, AbsDir, Coordinate, Grammar, Start, Stop};
 
-#[derive(Debug, Clone, Copy)]
-struct Step {
-    dir  : Direction,
-    dist : isize
+// What to show besides the answers
+enum Mode {
+    Answers,
+    // every step of the walk
+    Trace,
+    // the steps up to a stop
+    Replay(Stop),
+    // every location visited more than once
+    Revisits,
+    // the fewest steps to the same place, keeping the first revisit if true
+    Simplify(bool),
+    // the fewest steps back to the start facing the way it started
+    RouteHome,
 }
 
-// Convert a string into a vector of steps
-fn parse(text: &str) -> Result<Vec<Step>, String> {
-    let mut vec: Vec<Step> = Vec::new();
-    for entry in text.split(',') {
-        let e: &str = entry.trim();
-        if e.len() <= 1 {
-            return Result::Err(format!("Invalid input at '{}'",entry));
-        }
-        let mut chars = e.chars();
-        let dir = match chars.next().unwrap() {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _   => {return Result::Err(format!("Invalid direction at '{}'",entry))}
-        };
-        let dist = if let Ok(i) = e.split_at(1).1.parse::<isize>() {
-            i
-        } else {
-            return Result::Err(format!("Invalid distance at '{}'",entry));
-        };
-        vec.push(Step {dir: dir,dist: dist});
+fn parse_coordinate(text: &str) -> Option<Coordinate> {
+    let mut parts = text.split(',').map(|p| p.trim().parse::<isize>());
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Some(Coordinate {x, y}),
+        _ => None,
     }
-    Result::Ok(vec)
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct Coordinate {
-    x : isize,
-    y : isize
 }
 
-enum AbsDir { North, East, South, West }
-
-// Determine the new absolute direction based on the relative update
-fn turn(a: AbsDir, t: Direction) -> AbsDir {
-    match t {
-        Direction::Left => match a {
-            AbsDir::North => AbsDir::West,
-            AbsDir::West  => AbsDir::South,
-            AbsDir::South => AbsDir::East,
-            AbsDir::East  => AbsDir::North
-        },
-        Direction::Right => match a {
-            AbsDir::North => AbsDir::East,
-            AbsDir::East  => AbsDir::South,
-            AbsDir::South => AbsDir::West,
-            AbsDir::West  => AbsDir::North,
-        }
-    }
+// e.g. "3,-4,E"
+fn parse_start(text: &str) -> Option<Start> {
+    let (at, facing) = text.split_at(text.rfind(',')?);
+    let facing = match &facing[1..] {
+        "N" => AbsDir::North,
+        "E" => AbsDir::East,
+        "S" => AbsDir::South,
+        "W" => AbsDir::West,
+        _ => return None,
+    };
+    Some(Start { at: parse_coordinate(at)?, facing })
 }
 
-// calculate the final destination coordinates
-// assume the following coordinate system mapping
-// -x w
-// x  e
-// -y s
-// y  n
-fn calc_dest(steps: &Vec<Step>) -> Coordinate {
-    let mut facing = AbsDir::North;
-    let mut location = Coordinate {x:0,y:0};
-    for step in steps {
-        facing = turn(facing, step.dir);
-        match facing {
-            AbsDir::North => location.y += step.dist,
-            AbsDir::South => location.y -= step.dist,
-            AbsDir::East  => location.x += step.dist,
-            AbsDir::West  => location.x -= step.dist,
+// --extended and --start come before the mode
+fn parse_options(mut args: &[String]) -> Option<(Grammar, Start, Mode)> {
+    let (mut grammar, mut start) = (Grammar::Puzzle, Start::default());
+    loop {
+        match args.first().map(String::as_str) {
+            Some("--extended") => {
+                grammar = Grammar::Extended;
+                args = &args[1..];
+            }
+            Some("--start") if args.len() >= 2 => {
+                start = parse_start(&args[1])?;
+                args = &args[2..];
+            }
+            _ => break,
         }
     }
-    location
+    parse_mode(args).map(|mode| (grammar, start, mode))
 }
 
-// Same as calc_dest but tracks the path and checks for the first location visited twice
-fn calc_dest2(steps: &Vec<Step>) -> Coordinate {
-    let mut facing = AbsDir::North;
-    let mut location = Coordinate {x:0,y:0};
-    let mut path : Vec<Coordinate> = Vec::new();
-    path.reserve(steps.len() + 1);
-    path.push(location);
-    for step in steps {
-        facing = turn(facing, step.dir);
-        for _ in 0..step.dist {
-            match facing {
-                AbsDir::North => location.y += 1,
-                AbsDir::South => location.y -= 1,
-                AbsDir::East  => location.x += 1,
-                AbsDir::West  => location.x -= 1,
-            }
-            // leave early if we already visited this spot
-            // not sure why I need to borrow for the comparison because Coordinates are Copy.
-            if let Some(_) = path.iter().position(
-                |c| c == &location ) {return location};
-            // otherwise we save it and keep going
-            path.push(location);
-        }
+fn parse_mode(args: &[String]) -> Option<Mode> {
+    match args.len() {
+        0 => Some(Mode::Answers),
+        1 if args[0] == "--trace" => Some(Mode::Trace),
+        1 if args[0] == "--revisits" => Some(Mode::Revisits),
+        1 if args[0] == "--simplify" => Some(Mode::Simplify(false)),
+        1 if args[0] == "--simplify-keeping-revisit" => Some(Mode::Simplify(true)),
+        1 if args[0] == "--route-home" => Some(Mode::RouteHome),
+        2 if args[0] == "--replay-to-step" => args[1].parse().ok().map(|i| Mode::Replay(Stop::Step(i))),
+        2 if args[0] == "--replay-to-visit" => parse_coordinate(&args[1]).map(|c| Mode::Replay(Stop::Visit(c))),
+        _ => None,
     }
-    location
 }
 
 fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let options = if args.len() >= 2 { parse_options(&args[1..args.len() - 1]) } else { None };
     // get the file contents as an Option
-    let option = match env::args().count() {
-        // correct number of args? try to read the file
-        2 => {
-            let fname = env::args().nth(1).unwrap();
-            match File::open(&fname) {
+    let option = match options {
+        // correct args? try to read the file
+        Some(_) => {
+            let fname = args.last().unwrap();
+            match File::open(fname) {
                 // create string with file contents
                 Ok(mut x) => {
                     let mut s = String::new();
@@ -137,20 +106,58 @@ fn main() {
         // otherwise print usage
         _ => {
             let name = env::args().nth(0).unwrap();
-            println!("Usage: {} input-filename", &name);
+            println!("Usage: {} [--extended] [--start X,Y,N|E|S|W] [--trace | --revisits |\n       \
+                      --replay-to-step INDEX | --replay-to-visit X,Y | --simplify |\n       \
+                      --simplify-keeping-revisit | --route-home] input-filename", &name);
             Option::None
         }
     };
 
     // if there is a string, parse it
     if let Some(s) = option {
-        let v = parse(&s).unwrap();
-        let dest = calc_dest(&v);
+        let (grammar, start, mode) = options.unwrap();
+        let v = match parse_with(&s, grammar) {
+            Ok(v) => v,
+            Err(e) => {
+                println!("{}", e);
+                process::exit(1);
+            }
+        };
+        match mode {
+            Mode::Trace => for t in trace_from(&v, start) {
+                println!("{}", t);
+            },
+            Mode::Replay(stop) => match replay_from(&v, start, stop) {
+                Some(walked) => {
+                    for t in &walked {
+                        println!("{}", t);
+                    }
+                    let at = walked.last().map_or(start.at, |t| t.to);
+                    println!("Stopped at {} after {} steps", at, walked.len());
+                }
+                None => println!("The walk never stops at {:?}", stop),
+            },
+            Mode::Revisits => for r in revisits_from(&v, start) {
+                let by = r.steps.iter()
+                          .map(|s| s.map_or("the start".to_string(), |i| format!("step {}", i)))
+                          .collect::<Vec<_>>();
+                println!("{} visited {} times, by {}", r.at, r.visits(), by.join(", "));
+            },
+            Mode::Simplify(false) => println!("{}", render(&simplify_from(&v, start))),
+            Mode::Simplify(true) => println!("{}", render(&simplify_keeping_revisit_from(&v, start))),
+            Mode::RouteHome => println!("{}", render(&route_home_from(&v, start))),
+            _ => {}
+        }
+        // distances are measured from the start, which is the origin unless
+        // --start moved it
+        let dest = calc_dest_from(&v, start);
         println!("Destination (part1): {:?}. Distance: {}",
-                 dest, dest.x.abs()+dest.y.abs());
-        let pt2_dest = calc_dest2(&v);
-        println!("Destination (part2): {:?}. Distance: {}",
-                 pt2_dest, pt2_dest.x.abs()+pt2_dest.y.abs());
+                 dest, (dest.x-start.at.x).abs()+(dest.y-start.at.y).abs());
+        match calc_dest2_from(&v, start) {
+            Some(pt2_dest) => println!("Destination (part2): {:?}. Distance: {}",
+                                       pt2_dest, (pt2_dest.x-start.at.x).abs()+(pt2_dest.y-start.at.y).abs()),
+            None => println!("Destination (part2): none, no location is visited twice"),
+        }
     } else {
         process::exit(1);
     }
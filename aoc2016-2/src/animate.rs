@@ -0,0 +1,162 @@
+// Plays back a walk over the keypad in the terminal: the keypad is redrawn for
+// every direction with the finger's key highlighted, moves that run into a
+// wall or a hole marked as blocked, and the key pressed at the end of each line
+// flashed. It goes through go and go_pt2 themselves, so what you see is what
+// code and code_pt2 do.
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use {Coordinate, Direction};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Keypad {
+    // 1-9, for part 1
+    Square,
+    // the diamond of PT2_KEYS, for part 2
+    Diamond,
+}
+
+impl Keypad {
+    fn size(self) -> i8 {
+        match self {
+            Keypad::Square => 3,
+            Keypad::Diamond => 5,
+        }
+    }
+
+    fn start(self) -> Coordinate {
+        let mut at = Coordinate { x: 0, y: 0 };
+        match self {
+            Keypad::Square => at.from_key(5),
+            Keypad::Diamond => at.from_key_pt2(5),
+        }
+        at
+    }
+
+    // The key at a position, None for the holes around the diamond
+    fn key(self, at: Coordinate) -> Option<i8> {
+        match self {
+            Keypad::Square => Some(at.as_key()),
+            Keypad::Diamond => at.as_key_pt2(),
+        }
+    }
+
+    fn go(self, at: &mut Coordinate, dir: &Direction) {
+        match self {
+            Keypad::Square => at.go(dir),
+            Keypad::Diamond => at.go_pt2(dir),
+        }
+    }
+}
+
+// One picture of the playback: after following a direction, or pressing the
+// key at the end of a line when `dir` is None
+#[derive(Debug, PartialEq, Clone)]
+pub struct Frame {
+    // which line of directions, from 0
+    pub line: usize,
+    pub dir: Option<Direction>,
+    pub at: Coordinate,
+    // false if a wall or hole kept the finger where it was
+    pub moved: bool,
+    // the keys pressed so far, in hex
+    pub code: String,
+}
+
+pub fn frames(keypad: Keypad, key_vecs: &[Vec<Direction>]) -> Vec<Frame> {
+    let mut at = keypad.start();
+    let mut code = String::new();
+    let mut frames = Vec::new();
+    for (line, dirs) in key_vecs.iter().enumerate() {
+        for dir in dirs {
+            let before = at;
+            keypad.go(&mut at, dir);
+            frames.push(Frame { line, dir: Some(*dir), at, moved: at != before, code: code.clone() });
+        }
+        code.push_str(&format!("{:x}", keypad.key(at).expect("the finger is off the keypad")));
+        frames.push(Frame { line, dir: None, at, moved: false, code: code.clone() });
+    }
+    frames
+}
+
+// The keypad with the finger's key in brackets, or stars when it's pressed,
+// then what happened. With `colour` the finger is also shown in reverse video,
+// red when blocked and green when pressed.
+pub fn draw(keypad: Keypad, frame: &Frame, colour: bool) -> String {
+    let mut text = String::new();
+    for y in 0..keypad.size() {
+        for x in 0..keypad.size() {
+            let here = Coordinate { x, y };
+            let cell = match keypad.key(here) {
+                None => "   ".to_string(),
+                Some(key) if here != frame.at => format!(" {:x} ", key),
+                Some(key) if frame.dir.is_none() => format!("*{:x}*", key),
+                Some(key) => format!("[{:x}]", key),
+            };
+            let style = match (here == frame.at, frame.dir.is_some(), frame.moved) {
+                (false, _, _) => "",
+                (true, false, _) => "\x1b[1;32;7m",
+                (true, true, false) => "\x1b[31;7m",
+                (true, true, true) => "\x1b[7m",
+            };
+            if colour && !style.is_empty() {
+                text.push_str(&format!("{}{}\x1b[0m", style, cell));
+            } else {
+                text.push_str(&cell);
+            }
+        }
+        text.push('\n');
+    }
+    text.push_str(&match frame.dir {
+        Some(dir) if frame.moved => format!("line {}: {}\n", frame.line + 1, dir),
+        Some(dir) => format!("line {}: {} blocked\n", frame.line + 1, dir),
+        None => format!("line {}: pressed {:x}\n", frame.line + 1, keypad.key(frame.at).unwrap()),
+    });
+    text.push_str(&format!("code: {}\n", frame.code));
+    text
+}
+
+// Redraw each frame in place, `delay` apart and holding key presses for longer.
+// When the output isn't a terminal, the frames are just written one after
+// another.
+pub fn play<W: Write>(keypad: Keypad, key_vecs: &[Vec<Direction>], delay: Duration, tty: bool,
+                      out: &mut W) -> io::Result<()> {
+    if tty {
+        write!(out, "\x1b[2J")?;
+    }
+    for frame in frames(keypad, key_vecs) {
+        let text = draw(keypad, &frame, tty);
+        if !tty {
+            writeln!(out, "{}", text)?;
+            continue;
+        }
+        write!(out, "\x1b[H")?;
+        for line in text.lines() {
+            writeln!(out, "{}\x1b[K", line)?;
+        }
+        out.flush()?;
+        thread::sleep(if frame.dir.is_none() { delay * 5 } else { delay });
+    }
+    Ok(())
+}
+
+#[test]
+fn test_frames() {
+    use parse;
+
+    let square = frames(Keypad::Square, &[vec![Direction::Up]]);
+    let frames = frames(Keypad::Diamond, &parse("ULL\nRRDDD").unwrap());
+    let summary = frames.iter().map(|f| (f.dir.map(|d| d.to_string()), f.moved, f.code.clone()))
+                        .collect::<Vec<_>>();
+    let step = |dir: &str, moved| (Some(dir.to_string()), moved, String::new());
+    let press = |code: &str| (None, false, code.to_string());
+    assert_eq!(summary[..4], [step("U", false), step("L", false), step("L", false), press("5")]);
+    assert_eq!(frames.last().unwrap().code, "5d");
+    let text = draw(Keypad::Diamond, &frames[8], false);
+    let lines = text.lines().collect::<Vec<_>>();
+    assert_eq!(lines[2], " 5  6  7  8  9 ");
+    assert_eq!(lines[4..], ["      [d]      ", "line 2: D blocked", "code: 5"]);
+    assert_eq!(draw(Keypad::Square, &square[1], false).lines().next(), Some(" 1 *2* 3 "));
+}
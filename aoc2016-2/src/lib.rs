@@ -0,0 +1,213 @@
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+use std::fmt;
+
+pub mod animate;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction { Left, Right, Up, Down }
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Direction::Left  => write!(f, "L"),
+            Direction::Right => write!(f, "R"),
+            Direction::Up    => write!(f, "U"),
+            Direction::Down  => write!(f, "D"),
+        }
+    }
+}
+
+// Convert a string into a vector of directions
+pub fn parse(text: &str) -> Result<Vec<Vec<Direction>>, String> {
+    let mut vec: Vec<Vec<Direction>> = Vec::new();
+    // for each line
+    for entry in text.split_whitespace() {
+        // create a vector of steps based on the characters
+        let mut key_vec: Vec<Direction> = Vec::new();
+        let chars = entry.chars();
+        for c in chars {
+            let dir = match c {
+                'L' => Direction::Left,
+                'R' => Direction::Right,
+                'D' => Direction::Down,
+                'U' => Direction::Up,
+                _   => {return Result::Err(format!("Invalid direction at '{}'",entry))}
+            };
+            key_vec.push(dir);
+        }
+        vec.push(key_vec);
+    }
+    Result::Ok(vec)
+}
+
+// The reverse of parse, one line of directions per key
+pub fn render(keys: &[Vec<Direction>]) -> String {
+    keys.iter()
+        .map(|key| key.iter().map(|d| d.to_string()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Coordinate {
+    pub x : i8,
+    pub y : i8
+}
+
+// limit a number to the keypad coordinates
+fn limit<T: Ord>(input: T, min: T, max: T) -> T {
+    match () {
+        _ if input < min => min,
+        _ if input > max => max,
+        _ => input
+    }
+}
+
+pub const PT2_KEYS : [Option<i8>; 25] =
+    [ None,    None,     Some(1),  None,     None,
+      None,    Some(2),  Some(3),  Some(4),  None,
+      Some(5), Some(6),  Some(7),  Some(8),  Some(9),
+      None,    Some(10), Some(11), Some(12), None,
+      None,    None,     Some(13), None,     None ];
+
+impl Coordinate {
+
+    // Keypad maps to coordinates like so
+    //    0 1 2
+    //    ----- x
+    // 0 |1 2 3
+    // 1 |4 5 6
+    // 2 |7 8 9
+    //   y
+    // Return the integer key represented by the coordinate
+    pub fn as_key(&self) -> i8 { 1+self.x+3*self.y }
+
+    // set the coordinate based on the keypad. see mapping in as_key
+    pub fn from_key(&mut self, key: i8) {
+        self.x = limit(key % 3 - 1,0,2);
+        self.y = limit(key / 3    ,0,2);
+    }
+
+    // Keypad maps to coordinates like so
+    //    0 1 2 3 4
+    //    --------- x
+    // 0 |    1
+    // 1 |  2 3 4
+    // 2 |5 6 7 8 9
+    // 3 |  A B C
+    // 4 |    D
+    //   y
+    // Return the integer key represented by the coordinate (A-D are hex)
+    pub fn as_key_pt2(&self) -> Option<i8> { PT2_KEYS[(self.x+5*self.y) as usize] }
+
+    // set the coordinate based on the keypad. see mapping in PT2_KEYS
+    pub fn from_key_pt2(&mut self, key: i8) {
+        let lin_pos = PT2_KEYS.iter().enumerate().find(
+            |&x| match x.1 {
+                &Some(y) => key == y,
+                _       => false
+            }
+        ).unwrap().0;
+        self.x = (lin_pos % 5) as i8;
+        self.y = (lin_pos / 5) as i8;
+    }
+
+    // go to a coordinate limited to the keypad
+    pub fn go(&mut self, dir: &Direction) {
+        match dir {
+            &Direction::Left  => {self.x = limit(self.x-1,0,2)},
+            &Direction::Right => {self.x = limit(self.x+1,0,2)},
+            &Direction::Up    => {self.y = limit(self.y-1,0,2)},
+            &Direction::Down  => {self.y = limit(self.y+1,0,2)},
+        }
+    }
+
+    // go to a coordinate limited to the part 2 keypad
+    pub fn go_pt2(&mut self, dir: &Direction) {
+        let new_coord = match dir {
+            &Direction::Left  => Coordinate{x:limit(self.x-1,0,4), y:self.y},
+            &Direction::Right => Coordinate{x:limit(self.x+1,0,4), y:self.y},
+            &Direction::Up    => Coordinate{y:limit(self.y-1,0,4), x:self.x},
+            &Direction::Down  => Coordinate{y:limit(self.y+1,0,4), x:self.x},
+        };
+        // if its a valid key then update coordinate
+        if let Some(_) = new_coord.as_key_pt2() {
+            *self = new_coord;
+            //self.x = new_coord.x;
+            //self.y = new_coord.y;
+        }
+    }
+
+}
+
+// Follow each line of directions from the 5 key and collect the keys pressed
+pub fn code(key_vecs: &[Vec<Direction>]) -> Vec<i8> {
+    let mut dest = Coordinate {x:0,y:0};
+    dest.from_key(5); // instructions say we start at 5
+    key_vecs.iter().map(|v| {
+        for step in v { dest.go(step) };
+        dest.as_key()
+    }).collect()
+}
+
+// Same as code but on the part 2 keypad
+pub fn code_pt2(key_vecs: &[Vec<Direction>]) -> Vec<i8> {
+    let mut dest = Coordinate {x:0,y:0};
+    dest.from_key_pt2(5); // instructions say we start at 5
+    key_vecs.iter().map(|v| {
+        for step in v { dest.go_pt2(step) };
+        dest.as_key_pt2().unwrap()
+    }).collect()
+}
+
+#[cfg(test)]
+fn keys() -> impl proptest::strategy::Strategy<Value = Vec<Vec<Direction>>> {
+    use proptest::prelude::*;
+    let dir = prop_oneof![Just(Direction::Left), Just(Direction::Right),
+                          Just(Direction::Up), Just(Direction::Down)];
+    prop::collection::vec(prop::collection::vec(dir, 1..100), 1..10)
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn parse_never_panics(text in "\\PC*") {
+        let _ = parse(&text);
+    }
+
+    #[test]
+    fn parse_round_trips(keys in keys()) {
+        prop_assert_eq!(parse(&(render(&keys) + "\n")), Ok(keys));
+    }
+
+    #[test]
+    fn render_round_trips(text in "[LRUD]{1,100}(\n[LRUD]{1,100}){0,9}") {
+        prop_assert_eq!(render(&parse(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn parse_rejects_bad_directions(keys in keys(), bad in "[^LRUD\\s]", at in 0..10usize) {
+        let mut lines = render(&keys).lines().map(String::from).collect::<Vec<_>>();
+        let at = at % lines.len();
+        lines[at].push_str(&bad);
+        let err = parse(&lines.join("\n")).unwrap_err();
+        prop_assert!(err.contains(&lines[at]), "{} doesn't mention {}", err, lines[at]);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    extern crate serde_json;
+    let keys = parse("UL\nD").unwrap();
+    let json = serde_json::to_string(&keys).unwrap();
+    assert_eq!(json, r#"[["Up","Left"],["Down"]]"#);
+    assert_eq!(serde_json::from_str::<Vec<Vec<Direction>>>(&json).unwrap(), keys);
+}
@@ -1,133 +1,41 @@
+extern crate aoc2016_2;
+
 use std::env;
 use std::fs::File;
+use std::io::{self, IsTerminal};
 use std::io::prelude::*;
 use std::process;
+use std::time::Duration;
 
-#[derive(Debug, Clone, Copy)]
-enum Direction { Left, Right, Up, Down }
-
-// Convert a string into a vector of directions
-fn parse(text: &str) -> Result<Vec<Vec<Direction>>, String> {
-    let mut vec: Vec<Vec<Direction>> = Vec::new();
-    // for each line
-    for entry in text.split_whitespace() {
-        // create a vector of steps based on the characters
-        let mut key_vec: Vec<Direction> = Vec::new();
-        let chars = entry.chars();
-        for c in chars {
-            let dir = match c {
-                'L' => Direction::Left,
-                'R' => Direction::Right,
-                'D' => Direction::Down,
-                'U' => Direction::Up,
-                _   => {return Result::Err(format!("Invalid direction at '{}'",entry))}
-            };
-            key_vec.push(dir);
-        }
-        vec.push(key_vec);
-    }
-    Result::Ok(vec)
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-struct Coordinate {
-    x : i8,
-    y : i8
-}
-
-// limit a number to the keypad coordinates
-fn limit<T: Ord>(input: T, min: T, max: T) -> T {
-    match () {
-        _ if input < min => min,
-        _ if input > max => max,
-        _ => input
-    }
-}
-
-const PT2_KEYS : [Option<i8>; 25] =
-    [ None,    None,     Some(1),  None,     None,
-      None,    Some(2),  Some(3),  Some(4),  None,
-      Some(5), Some(6),  Some(7),  Some(8),  Some(9),
-      None,    Some(10), Some(11), Some(12), None,
-      None,    None,     Some(13), None,     None ];
-
-impl Coordinate {
-
-    // Keypad maps to coordinates like so
-    //    0 1 2
-    //    ----- x
-    // 0 |1 2 3
-    // 1 |4 5 6
-    // 2 |7 8 9
-    //   y
-    // Return the integer key represented by the coordinate
-    fn as_key(&self) -> i8 { 1+self.x+3*self.y }
-
-    // set the coordinate based on the keypad. see mapping in as_key
-    fn from_key(&mut self, key: i8) {
-        self.x = limit(key % 3 - 1,0,2);
-        self.y = limit(key / 3    ,0,2);
-    }
-
-    // Keypad maps to coordinates like so
-    //    0 1 2 3 4
-    //    --------- x
-    // 0 |    1
-    // 1 |  2 3 4
-    // 2 |5 6 7 8 9
-    // 3 |  A B C
-    // 4 |    D
-    //   y
-    // Return the integer key represented by the coordinate (A-D are hex)
-    fn as_key_pt2(&self) -> Option<i8> { PT2_KEYS[(self.x+5*self.y) as usize] }
-
-    // set the coordinate based on the keypad. see mapping in PT2_KEYS
-    fn from_key_pt2(&mut self, key: i8) {
-        let lin_pos = PT2_KEYS.iter().enumerate().find(
-            |&x| match x.1 {
-                &Some(y) => key == y,
-                _       => false
-            }
-        ).unwrap().0;
-        self.x = (lin_pos % 5) as i8;
-        self.y = (lin_pos / 5) as i8;
-    }
-
-    // go to a coordinate limited to the keypad
-    fn go(&mut self, dir: &Direction) {
-        match dir {
-            &Direction::Left  => {self.x = limit(self.x-1,0,2)},
-            &Direction::Right => {self.x = limit(self.x+1,0,2)},
-            &Direction::Up    => {self.y = limit(self.y-1,0,2)},
-            &Direction::Down  => {self.y = limit(self.y+1,0,2)},
-        }
-    }
+use aoc2016_2::{parse, code, code_pt2};
+use aoc2016_2::animate::{play, Keypad};
 
-    // go to a coordinate limited to the part 2 keypad
-    fn go_pt2(&mut self, dir: &Direction) {
-        let new_coord = match dir {
-            &Direction::Left  => Coordinate{x:limit(self.x-1,0,4), y:self.y},
-            &Direction::Right => Coordinate{x:limit(self.x+1,0,4), y:self.y},
-            &Direction::Up    => Coordinate{y:limit(self.y-1,0,4), x:self.x},
-            &Direction::Down  => Coordinate{y:limit(self.y+1,0,4), x:self.x},
-        };
-        // if its a valid key then update coordinate
-        if let Some(_) = new_coord.as_key_pt2() {
-            *self = new_coord;
-            //self.x = new_coord.x;
-            //self.y = new_coord.y;
+// The keypad to animate and the time between frames, if --animate was given
+fn parse_animate(args: &[String]) -> Option<Option<(Keypad, Duration)>> {
+    let keypad = |part: &str| match part {
+        "1" => Some(Keypad::Square),
+        "2" => Some(Keypad::Diamond),
+        _ => None,
+    };
+    match args.len() {
+        0 => Some(None),
+        2 if args[0] == "--animate" => Some(Some((keypad(&args[1])?, Duration::from_millis(100)))),
+        4 if args[0] == "--animate" && args[2] == "--delay" => {
+            Some(Some((keypad(&args[1])?, Duration::from_millis(args[3].parse().ok()?))))
         }
+        _ => None,
     }
-
 }
 
 fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let animate = if args.len() >= 2 { parse_animate(&args[1..args.len() - 1]) } else { None };
     // get the file contents as an Option
-    let option = match env::args().count() {
-        // correct number of args? try to read the file
-        2 => {
-            let fname = env::args().nth(1).unwrap();
-            match File::open(&fname) {
+    let option = match animate {
+        // correct args? try to read the file
+        Some(_) => {
+            let fname = args.last().unwrap();
+            match File::open(fname) {
                 // create string with file contents
                 Ok(mut x) => {
                     let mut s = String::new();
@@ -148,7 +56,7 @@ fn main() {
         // otherwise print usage
         _ => {
             let name = env::args().nth(0).unwrap();
-            println!("Usage: {} input-filename", &name);
+            println!("Usage: {} [--animate PART [--delay MS]] input-filename", &name);
             Option::None
         }
     };
@@ -156,20 +64,18 @@ fn main() {
     // if there is a string, parse it
     if let Some(s) = option {
         let key_vecs = parse(&s).unwrap();
-        let mut dest = Coordinate {x:0,y:0};
-        dest.from_key(5); // instructions say we start at 5
-        let code : Vec<i8> = key_vecs.iter().map(|v| {
-            for step in v { dest.go(step) };
-            dest.as_key()
-        }).collect();
-        println!("{:?}", code);
-
-        let mut dest2 = Coordinate {x:0,y:0};
-        dest2.from_key_pt2(5); // instructions say we start at 5
-        let code : Vec<String> = key_vecs.iter().map(|v| {
-            for step in v { dest2.go_pt2(step) };
-            format!("{:x}",dest2.as_key_pt2().unwrap()) // make it hex
-        }).collect();
+        if let Some(Some((keypad, delay))) = animate {
+            let stdout = io::stdout();
+            let tty = stdout.is_terminal();
+            if let Err(e) = play(keypad, &key_vecs, delay, tty, &mut stdout.lock()) {
+                println!("Couldn't animate: {}", e);
+                process::exit(1);
+            }
+        }
+        println!("{:?}", code(&key_vecs));
+        let code : Vec<String> = code_pt2(&key_vecs).iter()
+            .map(|k| format!("{:x}",k)) // make it hex
+            .collect();
         println!("{:?}", code);
     } else {
         process::exit(1);
@@ -0,0 +1,128 @@
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle {
+    pub a : u32,
+    pub b : u32,
+    pub c : u32,
+}
+
+// Three right aligned columns, like the puzzle input, with at least one space
+// before each so long sides don't run together
+impl fmt::Display for Triangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, " {:>4} {:>4} {:>4}", self.a, self.b, self.c)
+    }
+}
+
+// Convert a string into a vector of directions
+pub fn parse(text: &str) -> Result<Vec<Triangle>, String> {
+    let mut vec: Vec<Triangle> = Vec::new();
+    // for each line
+    for (line, entry) in text.lines().enumerate() {
+        let mut nums : [u32; 3] = [0,0,0];
+        let mut count = 0;
+        for num_str in entry.split_whitespace() {
+            match num_str.parse::<u32>() {
+                Ok(n) if count < 3 => {nums[count] = n; count += 1;},
+                _ => {return Result::Err(format!("Bad input at line {}: '{}'",line + 1,entry));}
+            }
+        }
+        if count != 3 {
+            return Result::Err(format!("Expected three sides at line {}: '{}'",line + 1,entry));
+        }
+        vec.push(Triangle{a:nums[0],b:nums[1],c:nums[2]});
+    }
+    Result::Ok(vec)
+}
+
+// The reverse of parse, one triangle per line
+pub fn render(tris: &[Triangle]) -> String {
+    tris.iter().map(|t| t.to_string()).collect::<Vec<_>>().join("\n")
+}
+
+pub fn test_tri(t: &Triangle) -> bool {
+    t.a + t.b > t.c &&
+    t.b + t.c > t.a &&
+    t.c + t.a > t.b
+}
+
+// Count the valid triangles when each line is a triangle (part 1)
+pub fn count_valid(tris: &[Triangle]) -> usize {
+    let mut count : usize = 0;
+    for t in tris {
+        if test_tri(t) {count += 1};
+    }
+    count
+}
+
+// Count the valid triangles when they are read down the columns in groups of
+// three lines (part 2)
+pub fn count_valid_columns(tris: &[Triangle]) -> usize {
+    let mut count = 0;
+    let mut titer = tris.iter();
+    for _ in 0..tris.len()/3 {
+        let i = titer.next().unwrap();
+        let j = titer.next().unwrap();
+        let k = titer.next().unwrap();
+
+        if test_tri(&Triangle {a:i.a , b:j.a , c:k.a}) {count += 1};
+        if test_tri(&Triangle {a:i.b , b:j.b , c:k.b}) {count += 1};
+        if test_tri(&Triangle {a:i.c , b:j.c , c:k.c}) {count += 1};
+    }
+    count
+}
+
+#[cfg(test)]
+fn triangles() -> impl proptest::strategy::Strategy<Value = Vec<Triangle>> {
+    use proptest::prelude::*;
+    prop::collection::vec((any::<u32>(), any::<u32>(), any::<u32>())
+                              .prop_map(|(a, b, c)| Triangle { a, b, c }), 1..30)
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn parse_never_panics(text in "\\PC*") {
+        let _ = parse(&text);
+    }
+
+    #[test]
+    fn parse_round_trips(tris in triangles()) {
+        prop_assert_eq!(parse(&(render(&tris) + "\n")), Ok(tris));
+    }
+
+    #[test]
+    fn render_round_trips(text in "(( {2}[1-9][0-9]{2}| {3}[1-9][0-9]| {4}[0-9]){3}\n){0,29}( {2}[1-9][0-9]{2}| {3}[1-9][0-9]| {4}[0-9]){3}") {
+        prop_assert_eq!(render(&parse(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn parse_rejects_bad_lines(tris in triangles(), bad in " *[0-9]{1,3}( +[0-9]{1,3})?| *([0-9]{1,3} +){3}[0-9]{1,3}|.*[^0-9\\s].*",
+                               at in 0..30usize) {
+        let mut lines = render(&tris).lines().map(String::from).collect::<Vec<_>>();
+        let at = at % lines.len();
+        lines[at] = bad;
+        let err = parse(&lines.join("\n")).unwrap_err();
+        let line = format!("line {}:", at + 1);
+        prop_assert!(err.contains(&line), "{} doesn't mention {}", err, line);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    extern crate serde_json;
+    let tris = parse("  5 10 25").unwrap();
+    let json = serde_json::to_string(&tris).unwrap();
+    assert_eq!(json, r#"[{"a":5,"b":10,"c":25}]"#);
+    assert_eq!(serde_json::from_str::<Vec<Triangle>>(&json).unwrap(), tris);
+}
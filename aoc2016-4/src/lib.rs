@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+extern crate regex;
+use regex::Regex;
+
+#[cfg(test)]
+#[macro_use]
+extern crate proptest;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Room {
+    // the encrypted name, dashes and all
+    pub id : String,
+    pub check : String,
+    pub sector : usize,
+}
+
+// In puzzle syntax, e.g. "aaaaa-bbb-z-y-x-123[abxyz]"
+impl fmt::Display for Room {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}[{}]", self.id, self.sector, self.check)
+    }
+}
+
+impl Room {
+
+    pub fn is_real(&self) -> bool {
+        let mut letter_count : HashMap<char,usize> = HashMap::new();
+        for c in self.id.chars().filter(|&c| c != '-') {
+            let count = letter_count.entry(c).or_insert(0);
+            *count += 1;
+        }
+        let mut order = letter_count.iter().collect::<Vec<_>>();
+        // since sorting is stable this order of operations guarantees the output is sorted by
+        // number of occurances and then alphabetically
+        order.sort_by_key(|entry| entry.0); // sort alphabetically
+        order.sort_by(|a,b| b.1.cmp(a.1)); // sort by count
+        let real_sum = order.iter().take(5).map(|e| e.0).cloned().collect::<String>();
+        return real_sum == self.check;
+    }
+
+    // Rotate every letter of the name forward by the sector id
+    pub fn decrypt(&self) -> String {
+        self.id.chars()
+               .filter(|&c| c != '-')
+               .map(|c| shift(c,self.sector as u32))
+               .collect::<String>()
+    }
+}
+
+// Convert a string into a vector of directions
+pub fn parse(text: &str) -> Result<Vec<Room>, String> {
+    let mut rooms: Vec<Room> = Vec::new();
+    let re = Regex::new(r"^([a-z]+-)+([0-9]{1,9})\[([a-z]{5})\]$").unwrap();
+    // for each line
+    for (line, entry) in text.lines().enumerate() {
+        let caps = match re.captures(entry) {
+            Some(c) => c,
+            None => return Result::Err(format!("Invalid room at line {}: '{}'",line + 1,entry)),
+        };
+        let sector = caps.at(2).unwrap().parse::<usize>().unwrap();
+        let checksum = caps.at(3).unwrap();
+        // everything before the dash ahead of the sector
+        let name_len = entry.len() - caps.at(2).unwrap().len() - 8;
+        let room = entry[..name_len].to_string();
+
+        rooms.push(
+            Room {
+                id : room,
+                check : checksum.to_string(),
+                sector : sector
+            }
+        );
+    }
+    Result::Ok(rooms)
+}
+
+// The reverse of parse, one room per line
+pub fn render(rooms: &[Room]) -> String {
+    rooms.iter().map(|r| r.to_string()).collect::<Vec<_>>().join("\n")
+}
+
+pub fn sum_real_rooms(rooms: &Vec<Room>) -> usize {
+    return rooms.iter()
+                .filter(|x| x.is_real())
+                .map(|room| room.sector)
+                .sum();
+}
+
+// All the real rooms with `name` somewhere in their decrypted name
+pub fn find_rooms<'a>(rooms: &'a [Room], name: &'a str) -> impl Iterator<Item = &'a Room> + 'a {
+    rooms.iter()
+         .filter(|r| r.is_real())
+         .filter(move |r| r.decrypt().contains(name))
+}
+
+const BASE : u32 = 'a' as u32;
+const MOD  : u32 = 'z' as u32 - BASE + 1;
+
+pub fn shift(c: char, count: u32) -> char {
+    ((((c as u32) - BASE + count) % MOD) + BASE) as u8 as char
+}
+
+#[test]
+fn test_valid() {
+    let test_input =
+        "aaaaa-bbb-z-y-x-123[abxyz]\n\
+         a-b-c-d-e-f-g-h-987[abcde]\n\
+         not-a-real-room-404[oarel]\n\
+         totally-real-room-200[decoy]";
+    let rooms = parse(test_input).unwrap();
+    assert_eq!(sum_real_rooms(&rooms), 1514);
+}
+
+#[test]
+fn test_shift() {
+    assert_eq!(shift('z',1),'a');
+    assert_eq!(shift('a',1),'b');
+}
+
+#[cfg(test)]
+fn rooms() -> impl proptest::strategy::Strategy<Value = Vec<(Vec<String>, usize, String)>> {
+    use proptest::prelude::*;
+    prop::collection::vec((prop::collection::vec("[a-z]{1,10}", 1..6), 0..1000usize, "[a-z]{5}"), 1..20)
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn parse_never_panics(text in "\\PC*") {
+        let _ = parse(&text);
+    }
+
+    #[test]
+    fn solving_never_panics(text in "([a-z]{1,3}-){1,5}[0-9]{1,9}\\[[a-z]{5}\\]") {
+        for room in parse(&text).unwrap() {
+            let _ = (room.is_real(), room.decrypt());
+        }
+    }
+
+    #[test]
+    fn parse_round_trips(rooms in rooms()) {
+        let text = rooms.iter()
+                        .map(|&(ref name, sector, ref check)| format!("{}-{}[{}]", name.join("-"), sector, check))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+        let expected = rooms.iter()
+                            .map(|&(ref name, sector, ref check)| Room { id: name.join("-"), check: check.clone(), sector })
+                            .collect::<Vec<_>>();
+        prop_assert_eq!(parse(&text), Ok(expected));
+    }
+
+    #[test]
+    fn render_round_trips(text in "(([a-z]{1,10}-){1,5}(0|[1-9][0-9]{0,3})\\[[a-z]{5}\\]\n){0,19}\
+                                   ([a-z]{1,10}-){1,5}(0|[1-9][0-9]{0,3})\\[[a-z]{5}\\]") {
+        prop_assert_eq!(render(&parse(&text).unwrap()), text);
+    }
+
+    #[test]
+    fn parse_rejects_bad_rooms(bad in "[a-z-]*[0-9]*(\\[[a-z]{0,4}\\])?|.*[^a-z0-9\\[\\]-].*") {
+        let text = format!("aaaaa-bbb-z-y-x-123[abxyz]\n{}\nnot-a-real-room-404[oarel]", bad);
+        prop_assert!(parse(&text).unwrap_err().contains("line 2:"));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    extern crate serde_json;
+    let rooms = parse("a-b-c-d-e-f-g-h-987[abcde]").unwrap();
+    let json = serde_json::to_string(&rooms).unwrap();
+    assert_eq!(json, r#"[{"id":"a-b-c-d-e-f-g-h","check":"abcde","sector":987}]"#);
+    assert_eq!(serde_json::from_str::<Vec<Room>>(&json).unwrap(), rooms);
+}
@@ -0,0 +1,396 @@
+// Spreading a password search over worker processes, possibly on other
+// machines. A coordinator listens on a TCP address or a Unix socket and hands
+// out chunks of indices; workers connect, search each chunk and send back the
+// indices of the hits. The coordinator checks each hit, then adds them to both
+// passwords in index order, so the result is the same as a search in one
+// process however the chunks were shared out.
+//
+// The protocol is one line of text per message. A worker sends
+//
+//     NEXT                       when it's ready for its first chunk
+//     HITS <start> <index>...    with the hits in the chunk it was given
+//
+// and the coordinator answers each with
+//
+//     CHUNK <salt> <start> <end> a chunk of indices to search
+//     DONE                       when both passwords are found
+//
+// A chunk is handed out again if its worker goes away, stops answering for
+// TIMEOUT, sends a line longer than MAX_LINE or sends back hits that don't
+// check out; a faulty worker is disconnected rather than ending the search.
+//
+// Workers are trusted to be buggy rather than hostile. Every hit they report
+// is hashed again, so a made up hit is always caught, but a hit left out can
+// only be caught by searching the chunk again. The coordinator does that for a
+// worker's first chunk and every AUDIT-th one after, which catches a worker
+// that's wrong all the time straight away and one that's wrong now and then
+// eventually, for a sixteenth of the work. One that left out hits from chunks
+// that weren't audited can still spoil the passwords.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crypto::md5::Md5;
+
+use search::{Extract, Hit, Password, Search, CHUNK_SIZE};
+use Progress;
+
+// How long a worker can take over a chunk before it's taken to have gone away
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+// The longest line a worker can send. A chunk has a hit every million or so
+// indices, so an honest HITS line is nowhere near this.
+pub const MAX_LINE: u64 = 4096;
+
+// One chunk in this many from each worker is searched again by the coordinator
+pub const AUDIT: usize = 16;
+
+type Reader = Box<dyn BufRead + Send>;
+type Writer = Box<dyn Write + Send>;
+
+pub enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+// Addresses starting with unix: are a socket path, anything else is host:port
+pub fn listen(address: &str) -> Result<Listener, String> {
+    let error = |e: io::Error| format!("Couldn't listen on '{}': {}", address, e);
+    let listener = match address.strip_prefix("unix:") {
+        #[cfg(unix)]
+        Some(path) => Listener::Unix(UnixListener::bind(path).map_err(error)?),
+        #[cfg(not(unix))]
+        Some(_) => return Err("Unix sockets aren't supported here".to_string()),
+        None => Listener::Tcp(TcpListener::bind(address).map_err(error)?),
+    };
+    // so the coordinator can check for finished chunks between connections
+    let nonblocking = match listener {
+        Listener::Tcp(ref l) => l.set_nonblocking(true),
+        #[cfg(unix)]
+        Listener::Unix(ref l) => l.set_nonblocking(true),
+    };
+    nonblocking.map_err(error)?;
+    Ok(listener)
+}
+
+impl Listener {
+    // The address workers should connect to, with the port filled in if it
+    // was 0
+    pub fn address(&self) -> String {
+        match *self {
+            Listener::Tcp(ref l) => l.local_addr().map(|a| a.to_string()).unwrap_or_default(),
+            #[cfg(unix)]
+            Listener::Unix(ref l) => l.local_addr().ok()
+                                      .and_then(|a| a.as_pathname().map(|p| format!("unix:{}", p.display())))
+                                      .unwrap_or_default(),
+        }
+    }
+
+    // A new connection, or None if there isn't one waiting
+    fn accept(&self) -> io::Result<Option<(Reader, Writer)>> {
+        let result = match *self {
+            Listener::Tcp(ref l) => l.accept().and_then(|(s, _)| {
+                s.set_nonblocking(false)?;
+                s.set_read_timeout(Some(TIMEOUT))?;
+                Ok((Box::new(BufReader::new(s.try_clone()?)) as Reader, Box::new(s) as Writer))
+            }),
+            #[cfg(unix)]
+            Listener::Unix(ref l) => l.accept().and_then(|(s, _)| {
+                s.set_nonblocking(false)?;
+                s.set_read_timeout(Some(TIMEOUT))?;
+                Ok((Box::new(BufReader::new(s.try_clone()?)) as Reader, Box::new(s) as Writer))
+            }),
+        };
+        match result {
+            Ok(conn) => Ok(Some(conn)),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn connect(address: &str) -> Result<(Reader, Writer), String> {
+    let error = |e: io::Error| format!("Couldn't connect to '{}': {}", address, e);
+    match address.strip_prefix("unix:") {
+        #[cfg(unix)]
+        Some(path) => {
+            let s = UnixStream::connect(path).map_err(error)?;
+            Ok((Box::new(BufReader::new(s.try_clone().map_err(error)?)), Box::new(s)))
+        }
+        #[cfg(not(unix))]
+        Some(_) => Err("Unix sockets aren't supported here".to_string()),
+        None => {
+            let s = TcpStream::connect(address).map_err(error)?;
+            Ok((Box::new(BufReader::new(s.try_clone().map_err(error)?)), Box::new(s)))
+        }
+    }
+}
+
+// What the coordinator's connections share
+struct Chunks {
+    salt: String,
+    next: usize,
+    // chunks whose worker went away
+    lost: Vec<usize>,
+    // the checked hits in each finished chunk, by its start
+    finished: BTreeMap<usize, Vec<Hit>>,
+    done: bool,
+}
+
+impl Chunks {
+    fn take(&mut self) -> Option<usize> {
+        if self.done {
+            return None;
+        }
+        Some(self.lost.pop().unwrap_or_else(|| {
+            self.next += CHUNK_SIZE;
+            self.next - CHUNK_SIZE
+        }))
+    }
+}
+
+fn parse_hits(line: &str) -> Option<(usize, Vec<usize>)> {
+    let mut words = line.split_whitespace();
+    if words.next() != Some("HITS") {
+        return None;
+    }
+    let start = words.next()?.parse().ok()?;
+    let hits = words.map(|w| w.parse().ok()).collect::<Option<Vec<_>>>()?;
+    Some((start, hits))
+}
+
+// The hits a worker reported for the chunk from `start`, if they're in the
+// chunk, in increasing order and really are hits. An `audit` searches the
+// whole chunk again, so hits left out are caught too.
+fn check(search: &mut Search<Md5>, start: usize, indices: &[usize], audit: bool) -> Option<Vec<Hit>> {
+    if audit {
+        let hits = search.chunk(start..start + CHUNK_SIZE);
+        let same = hits.iter().map(|h| h.index).eq(indices.iter().cloned());
+        return if same { Some(hits) } else { None };
+    }
+    let mut hits = Vec::new();
+    let mut next = start;
+    for &index in indices {
+        if index < next || index >= start + CHUNK_SIZE {
+            return None;
+        }
+        hits.push(search.chunk(index..index + 1).pop()?);
+        next = index + 1;
+    }
+    Some(hits)
+}
+
+// Talk to one worker until it goes away, gets something wrong or there's
+// nothing left to do
+fn serve(chunks: Arc<Mutex<Chunks>>, mut reader: Reader, mut writer: Writer, tx: Sender<()>) {
+    let mut search = Search::new(Md5::new(), &chunks.lock().unwrap().salt);
+    let mut current = None;
+    let mut returned = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        // a timeout counts as the worker going away, and a line that's cut
+        // off at MAX_LINE as it getting something wrong
+        if (&mut reader).take(MAX_LINE).read_line(&mut line).unwrap_or(0) == 0 || !line.ends_with('\n') {
+            break;
+        }
+        let hits = match (parse_hits(&line), current) {
+            // check the worker's sums rather than trusting them
+            (Some((start, indices)), Some(c)) if c == start => {
+                returned += 1;
+                match check(&mut search, start, &indices, returned % AUDIT == 1) {
+                    Some(hits) => Some((start, hits)),
+                    None => break,
+                }
+            }
+            _ => None,
+        };
+        let mut chunks = chunks.lock().unwrap();
+        match (hits, current) {
+            (Some((start, hits)), _) => {
+                chunks.finished.insert(start, hits);
+                let _ = tx.send(());
+            }
+            // anything else just asks for a chunk, but one still out is
+            // handed out again rather than dropped
+            (None, Some(start)) => chunks.lost.push(start),
+            (None, None) => {}
+        }
+        current = chunks.take();
+        let reply = match current {
+            Some(start) => format!("CHUNK {} {} {}\n", chunks.salt, start, start + CHUNK_SIZE),
+            None => "DONE\n".to_string(),
+        };
+        drop(chunks);
+        if writer.write_all(reply.as_bytes()).is_err() || current.is_none() {
+            break;
+        }
+    }
+    if let Some(start) = current {
+        chunks.lock().unwrap().lost.push(start);
+    }
+}
+
+// Hand out chunks of the day 5 search for `salt` to whoever connects until
+// both `length` character passwords are found, passing the progress to
+// `report` as finished chunks come in
+pub fn coordinate<F: FnMut(&Progress)>(listener: &Listener, salt: &str, length: usize,
+                                       mut report: F) -> Result<(String, String), String> {
+    if salt.is_empty() || salt.contains(char::is_whitespace) {
+        return Err(format!("Can't share out a search for '{}'", salt));
+    }
+    let chunks = Arc::new(Mutex::new(Chunks {
+        salt: salt.to_string(), next: 0, lost: Vec::new(), finished: BTreeMap::new(), done: false,
+    }));
+    let (tx, rx) = mpsc::channel();
+    let search = Search::new(Md5::new(), salt);
+    let mut passwords = (Password::new(length, Extract::InOrder), Password::new(length, Extract::Positioned));
+    let mut searched = 0;
+    let start = Instant::now();
+    loop {
+        loop {
+            match listener.accept() {
+                Ok(Some((reader, writer))) => {
+                    let (chunks, tx) = (chunks.clone(), tx.clone());
+                    thread::spawn(move || serve(chunks, reader, writer, tx));
+                }
+                Ok(None) => break,
+                // e.g. a worker that hung up before it was accepted, which
+                // shouldn't stop the search
+                Err(e) => {
+                    eprintln!("Couldn't accept a worker: {}", e);
+                    break;
+                }
+            }
+        }
+        if rx.recv_timeout(Duration::from_millis(50)).is_err() {
+            continue;
+        }
+        let mut chunks = chunks.lock().unwrap();
+        let before = searched;
+        while let Some(hits) = chunks.finished.remove(&searched) {
+            for hit in hits {
+                passwords.0.add(&hit);
+                passwords.1.add(&hit);
+            }
+            searched += CHUNK_SIZE;
+        }
+        if let (Some(one), Some(two)) = (passwords.0.finished(), passwords.1.finished()) {
+            chunks.done = true;
+            return Ok((one, two));
+        }
+        if searched == before {
+            continue;
+        }
+        report(&Progress {
+            searched,
+            password: format!("{} {}", passwords.0.so_far(), passwords.1.so_far()),
+            remaining: passwords.0.hits_left().max(passwords.1.hits_left()) * search.hit_rate(),
+            elapsed: start.elapsed(),
+        });
+    }
+}
+
+// Search chunks for the coordinator at `address` until it says we're done.
+// Returns how many chunks this worker searched.
+pub fn work(address: &str) -> Result<usize, String> {
+    let (mut reader, mut writer) = connect(address)?;
+    let error = |e: io::Error| format!("Lost the coordinator: {}", e);
+    let mut search: Option<Search<Md5>> = None;
+    let mut count = 0;
+    let mut message = "NEXT".to_string();
+    loop {
+        writer.write_all(format!("{}\n", message).as_bytes()).map_err(error)?;
+        let mut line = String::new();
+        if reader.read_line(&mut line).map_err(error)? == 0 || line.trim() == "DONE" {
+            return Ok(count);
+        }
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let (salt, start, end) = match (words.first(), words.get(1), words.get(2).and_then(|w| w.parse().ok()),
+                                        words.get(3).and_then(|w| w.parse().ok())) {
+            (Some(&"CHUNK"), Some(salt), Some(start), Some(end)) if words.len() == 4 => (salt, start, end),
+            _ => return Err(format!("Unexpected message from the coordinator: '{}'", line.trim())),
+        };
+        if search.as_ref().is_none_or(|s| s.salt() != *salt) {
+            search = Some(Search::new(Md5::new(), salt));
+        }
+        let hits = search.as_mut().unwrap().chunk(start..end);
+        message = hits.iter().fold(format!("HITS {}", start), |m, hit| format!("{} {}", m, hit.index));
+        count += 1;
+    }
+}
+
+#[cfg(test)]
+fn search_with_workers(address: &str, length: usize) -> (String, String) {
+    let listener = listen(address).unwrap();
+    let address = listener.address();
+    // one worker that takes a chunk and goes away, one that claims the
+    // chunk's first index is a hit twice over, and one that sends a line
+    // that never ends
+    let faulty = [0, 1, 2].iter().map(|&fault| {
+        let (mut reader, mut writer) = connect(&address).unwrap();
+        thread::spawn(move || {
+            let mut line = String::new();
+            writer.write_all(b"NEXT\n").unwrap();
+            reader.read_line(&mut line).unwrap();
+            let start = line.split_whitespace().nth(2).unwrap().to_string();
+            let reply = match fault {
+                1 => format!("HITS {0} {0} {0}\n", start),
+                2 => format!("HITS {} {}", start, "0 ".repeat(MAX_LINE as usize)),
+                _ => return,
+            };
+            // the coordinator hangs up rather than handing out another chunk
+            let _ = writer.write_all(reply.as_bytes());
+            line.clear();
+            assert_eq!(reader.read_line(&mut line).unwrap_or(0), 0);
+        })
+    }).collect::<Vec<_>>();
+    let workers = (0..3).map(|_| {
+        let address = address.clone();
+        thread::spawn(move || work(&address))
+    }).collect::<Vec<_>>();
+    let passwords = coordinate(&listener, "abc", length, |_| {}).unwrap();
+    for worker in workers {
+        worker.join().unwrap().unwrap();
+    }
+    for worker in faulty {
+        worker.join().unwrap();
+    }
+    passwords
+}
+
+#[test]
+fn test_tcp() {
+    assert_eq!(search_with_workers("127.0.0.1:0", 3), ("18f".to_string(), "05a".to_string()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix() {
+    let path = ::std::env::temp_dir().join(format!("aoc2016-5-{}.sock", ::std::process::id()));
+    let _ = ::std::fs::remove_file(&path);
+    assert_eq!(search_with_workers(&format!("unix:{}", path.display()), 1), ("1".to_string(), "0".to_string()));
+    ::std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_check() {
+    let mut search = Search::new(Md5::new(), "abc");
+    let start = 3_230_000;
+    assert_eq!(check(&mut search, start, &[], false), Some(vec![]));
+    assert_eq!(check(&mut search, start, &[3_231_929], false).map(|h| h[0].index), Some(3_231_929));
+    assert_eq!(check(&mut search, start, &[3_231_929, 3_231_929], false), None);
+    assert_eq!(check(&mut search, start, &[3_231_930], false), None);
+    assert_eq!(check(&mut search, start - CHUNK_SIZE, &[3_231_929], false), None);
+    // only an audit notices the hit that was left out
+    assert_eq!(check(&mut search, start, &[], true), None);
+    assert_eq!(check(&mut search, start, &[3_231_929], true).map(|h| h.len()), Some(1));
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+extern crate crypto;
+
+pub mod distributed;
+pub mod md5x;
+pub mod progress;
+pub mod search;
+
+use crypto::digest::Digest;
+#[cfg(test)]
+use crypto::md5::Md5;
+
+use search::{find_password, Extract, Search};
+
+// How far a password search has got, passed to the progress hook after every
+// chunk of indices
+#[derive(Debug, Clone)]
+pub struct Progress {
+    pub searched : usize,
+    // the password so far, with '_' for characters not found yet
+    pub password : String,
+    // how many more indices we expect to search
+    pub remaining : f64,
+    pub elapsed : Duration,
+}
+
+impl Progress {
+    // Hashes per second
+    pub fn rate(&self) -> f64 {
+        self.searched as f64 / self.elapsed.as_secs_f64().max(1e-9)
+    }
+
+    pub fn fraction(&self) -> f64 {
+        self.searched as f64 / (self.searched as f64 + self.remaining)
+    }
+
+    pub fn eta(&self) -> Option<Duration> {
+        if self.searched == 0 { return None; }
+        Some(Duration::from_secs_f64(self.remaining / self.rate()))
+    }
+}
+
+pub fn calc_password_1<T: Digest + 'static>(hasher: T, input: &str) -> String {
+    calc_password_1_with_progress(hasher, input, |_| {})
+}
+
+pub fn calc_password_1_with_progress<T, F>(hasher: T, input: &str, report: F) -> String
+    where T: Digest + 'static, F: FnMut(&Progress)
+{
+    find_password(&mut Search::new(hasher, input), 8, Extract::InOrder, report)
+}
+
+pub fn calc_password_2<T: Digest + 'static>(hasher: T, input: &str) -> String {
+    calc_password_2_with_progress(hasher, input, |_| {})
+}
+
+pub fn calc_password_2_with_progress<T, F>(hasher: T, input: &str, report: F) -> String
+    where T: Digest + 'static, F: FnMut(&Progress)
+{
+    find_password(&mut Search::new(hasher, input), 8, Extract::Positioned, report)
+}
+
+#[test]
+fn test_example_1() {
+    assert_eq!(calc_password_1(Md5::new(),"abc"),"18f47a30");
+}
+
+#[test]
+fn test_example_2() {
+    assert_eq!(calc_password_2(Md5::new(),"abc"),"05ace8e3");
+}
@@ -1,87 +1,75 @@
-use std::io;
-use std::ops::Range;
-
+extern crate aoc2016_5;
 extern crate crypto;
 
-use crypto::digest::Digest;
-use crypto::md5::Md5;
+use std::env;
+use std::fs;
+use std::io;
 
-#[derive(Debug)]
-struct Pwchar {
-    chr : char,
-    pos : usize
-}
+use crypto::md5::Md5;
 
-fn calc_password_chunk<T: Digest>(hasher: &mut T,
-                                  input : &str,
-                                  range: Range<usize>) -> Vec<(char, char)> {
-    let mut result : Vec<(char,char)> = Vec::new();
-    let mut hash = vec![ 0u8; hasher.output_bytes() ];
-    for index in range {
-        let teststr = format!("{}{:01}",input,index);
-        hasher.reset();
-        hasher.input_str(&teststr);
-        hasher.result(&mut hash);
-        if hash[0..2] == [0u8; 2] {
-            if hash[2] < 0x10 {
-                let rstr = hasher.result_str();
-                result.push( (
-                    rstr.chars().nth(5).unwrap(),
-                    rstr.chars().nth(6).unwrap(),
-                    )
-                );
-            }
-        }
-    }
-    result
-}
+use aoc2016_5::{calc_password_1_with_progress, calc_password_2_with_progress};
+use aoc2016_5::distributed::{coordinate, listen, work};
+use aoc2016_5::progress::Reporter;
 
-fn calc_password_1<T: Digest>(mut hasher: T, input: &str) -> String {
-    const CHUNK_SIZE : usize = 10_000;
-    let mut index = 0;
-    let mut pwchars: Vec<char> = Vec::new();
-    while pwchars.len() < 8 {
-        let result = calc_password_chunk(&mut hasher, &input, index..index+CHUNK_SIZE);
-        pwchars.extend(result.into_iter().map(|r| r.0).collect::<Vec<_>>());
-        index += CHUNK_SIZE;
+// `coordinate ADDRESS` shares the search for the input out to workers started
+// with `work ADDRESS`, on this machine or others
+fn distributed(roomid: &str, address: &str) -> Result<(), String> {
+    let listener = listen(address)?;
+    println!("Waiting for workers on {}", listener.address());
+    let mut reporter = Reporter::new("parts 1 and 2");
+    let result = coordinate(&listener, roomid, 8, |p| reporter.report(p));
+    reporter.finish();
+    if let Some(path) = address.strip_prefix("unix:") {
+        let _ = fs::remove_file(path);
     }
-    let chars = pwchars.iter().map(|&x| x).collect::<String>();
-    chars[0..8].to_string()
-}
-
-fn calc_password_2<T: Digest>(mut hasher: T, input: &str) -> String {
-    const CHUNK_SIZE : usize = 10_000;
-    let mut index = 0;
-    let mut password = ['\0';8];
-    let mut done = [false;8];
-    'find_loop : loop {
-        let result = calc_password_chunk(&mut hasher, &input, index..index+CHUNK_SIZE);
-        for pwchar in result.iter()
-                            .map(|t| Pwchar {
-                                pos : t.0 as usize - '0' as usize,
-                                chr : t.1})
-                            .filter(|pwc| pwc.pos < 8) {
-            if done[pwchar.pos] {continue;} // skip any repeat chars
-            password[pwchar.pos] = pwchar.chr;
-            done[pwchar.pos] = true;
-            if done.iter().all(|&x| x) { break 'find_loop; }
-        }
-        index += CHUNK_SIZE;
-    }
-    password.iter().map(|&x| x).collect::<String>()
+    let (password1, password2) = result?;
+    println!("Found password for part 1: {}", password1);
+    println!("Found password for part 2: {}", password2);
+    Ok(())
 }
 
 fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let coordinator = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => None,
+        ["coordinate", address] => Some(address.to_string()),
+        ["work", address] => match work(address) {
+            Ok(count) => {
+                println!("Searched {} chunks", count);
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!("usage: {0} [coordinate ADDRESS | work ADDRESS]\n\
+                       where ADDRESS is host:port or unix:PATH", env::args().next().unwrap());
+            std::process::exit(2);
+        }
+    };
     println!("Enter puzzle input: ");
     let mut input = String::new();
     match io::stdin().read_line(&mut input) {
         Ok(num_bytes) if num_bytes > 1 => {
             let roomid = input.trim();
+            if let Some(address) = coordinator {
+                if let Err(e) = distributed(roomid, &address) {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+                std::process::exit(0);
+            }
             println!("Searching for password for '{}' (part 1)", roomid);
-            let password1 = calc_password_1(Md5::new(), &roomid);
+            let mut reporter = Reporter::new("part 1");
+            let password1 = calc_password_1_with_progress(Md5::new(), roomid, |p| reporter.report(p));
+            reporter.finish();
             println!("Found password for part 1: {}", password1);
             println!("Searching for password for '{}' (part 2)", roomid);
-            let password2 = calc_password_2(Md5::new(), &roomid);
+            let mut reporter = Reporter::new("part 2");
+            let password2 = calc_password_2_with_progress(Md5::new(), roomid, |p| reporter.report(p));
+            reporter.finish();
             println!("Found password for part 2: {}", password2);
             std::process::exit(0);
         },
@@ -90,13 +78,3 @@ fn main() {
     }
 
 }
-
-#[test]
-fn test_example_1() {
-    assert_eq!(calc_password_1(Md5::new(),"abc"),"18f47a30");
-}
-
-#[test]
-fn test_example_2() {
-    assert_eq!(calc_password_2(Md5::new(),"abc"),"05ace8e3");
-}
@@ -0,0 +1,363 @@
+// Multi-buffer MD5: hashing 4 (SSE2) or 8 (AVX2) short messages at once, one
+// in each 32 bit lane of a vector register. The searches only ever hash a salt
+// and an index, or a 32 digit hex digest when stretching, so every message fits
+// in a single 64 byte block and there's no need to handle longer ones.
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+// The longest message that still fits in one block with its padding
+pub const MAX_LEN: usize = 55;
+
+// A prefix followed by a number in decimal, built without allocating
+#[derive(Clone, Copy)]
+pub struct Message {
+    bytes: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl Message {
+    pub fn new(prefix: &[u8], mut n: usize) -> Message {
+        let mut digits = [0; 20];
+        let mut count = 0;
+        loop {
+            digits[count] = b'0' + (n % 10) as u8;
+            count += 1;
+            n /= 10;
+            if n == 0 { break; }
+        }
+        let len = prefix.len() + count;
+        assert!(len <= MAX_LEN, "message is over {} bytes", MAX_LEN);
+        let mut bytes = [0; MAX_LEN];
+        bytes[..prefix.len()].copy_from_slice(prefix);
+        for (byte, &digit) in bytes[prefix.len()..len].iter_mut().zip(digits[..count].iter().rev()) {
+            *byte = digit;
+        }
+        Message { bytes, len }
+    }
+}
+
+impl AsRef<[u8]> for Message {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backend {
+    Scalar,
+    Sse2,
+    Avx2,
+}
+
+impl Backend {
+    // The widest backend this CPU supports
+    pub fn detect() -> Backend {
+        [Backend::Avx2, Backend::Sse2].iter().cloned()
+                                      .find(|b| b.available())
+                                      .unwrap_or(Backend::Scalar)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    pub fn available(self) -> bool {
+        match self {
+            Backend::Scalar => true,
+            Backend::Sse2 => is_x86_feature_detected!("sse2"),
+            Backend::Avx2 => is_x86_feature_detected!("avx2"),
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    pub fn available(self) -> bool {
+        self == Backend::Scalar
+    }
+
+    // How many messages are hashed at once
+    pub fn lanes(self) -> usize {
+        match self {
+            Backend::Scalar => 1,
+            Backend::Sse2 => 4,
+            Backend::Avx2 => 8,
+        }
+    }
+}
+
+// The operations MD5 needs on a word, or on a vector of one word per lane
+trait Word: Copy {
+    const LANES: usize;
+    fn splat(x: u32) -> Self;
+    fn load(lanes: &[u32]) -> Self;
+    fn store(self, lanes: &mut [u32]);
+    fn add(self, other: Self) -> Self;
+    fn and(self, other: Self) -> Self;
+    fn or(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    fn not(self) -> Self;
+    fn rotl(self, n: u32) -> Self;
+}
+
+impl Word for u32 {
+    const LANES: usize = 1;
+    fn splat(x: u32) -> u32 { x }
+    fn load(lanes: &[u32]) -> u32 { lanes[0] }
+    fn store(self, lanes: &mut [u32]) { lanes[0] = self; }
+    fn add(self, other: u32) -> u32 { self.wrapping_add(other) }
+    fn and(self, other: u32) -> u32 { self & other }
+    fn or(self, other: u32) -> u32 { self | other }
+    fn xor(self, other: u32) -> u32 { self ^ other }
+    fn not(self) -> u32 { !self }
+    fn rotl(self, n: u32) -> u32 { self.rotate_left(n) }
+}
+
+// SSE2 is part of x86_64, so these need no feature checks
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy)]
+struct Sse2(__m128i);
+
+#[cfg(target_arch = "x86_64")]
+impl Word for Sse2 {
+    const LANES: usize = 4;
+    #[inline(always)]
+    fn splat(x: u32) -> Sse2 { unsafe { Sse2(_mm_set1_epi32(x as i32)) } }
+    #[inline(always)]
+    fn load(lanes: &[u32]) -> Sse2 {
+        assert!(lanes.len() >= 4);
+        unsafe { Sse2(_mm_loadu_si128(lanes.as_ptr() as *const __m128i)) }
+    }
+    #[inline(always)]
+    fn store(self, lanes: &mut [u32]) {
+        assert!(lanes.len() >= 4);
+        unsafe { _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, self.0) }
+    }
+    #[inline(always)]
+    fn add(self, other: Sse2) -> Sse2 { unsafe { Sse2(_mm_add_epi32(self.0, other.0)) } }
+    #[inline(always)]
+    fn and(self, other: Sse2) -> Sse2 { unsafe { Sse2(_mm_and_si128(self.0, other.0)) } }
+    #[inline(always)]
+    fn or(self, other: Sse2) -> Sse2 { unsafe { Sse2(_mm_or_si128(self.0, other.0)) } }
+    #[inline(always)]
+    fn xor(self, other: Sse2) -> Sse2 { unsafe { Sse2(_mm_xor_si128(self.0, other.0)) } }
+    #[inline(always)]
+    fn not(self) -> Sse2 { self.xor(Sse2::splat(!0)) }
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Sse2 {
+        unsafe {
+            Sse2(_mm_or_si128(_mm_sll_epi32(self.0, _mm_cvtsi32_si128(n as i32)),
+                              _mm_srl_epi32(self.0, _mm_cvtsi32_si128(32 - n as i32))))
+        }
+    }
+}
+
+// Only used inside functions compiled with AVX2 enabled, after checking the
+// CPU has it
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy)]
+struct Avx2(__m256i);
+
+#[cfg(target_arch = "x86_64")]
+impl Word for Avx2 {
+    const LANES: usize = 8;
+    #[inline(always)]
+    fn splat(x: u32) -> Avx2 { unsafe { Avx2(_mm256_set1_epi32(x as i32)) } }
+    #[inline(always)]
+    fn load(lanes: &[u32]) -> Avx2 {
+        assert!(lanes.len() >= 8);
+        unsafe { Avx2(_mm256_loadu_si256(lanes.as_ptr() as *const __m256i)) }
+    }
+    #[inline(always)]
+    fn store(self, lanes: &mut [u32]) {
+        assert!(lanes.len() >= 8);
+        unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, self.0) }
+    }
+    #[inline(always)]
+    fn add(self, other: Avx2) -> Avx2 { unsafe { Avx2(_mm256_add_epi32(self.0, other.0)) } }
+    #[inline(always)]
+    fn and(self, other: Avx2) -> Avx2 { unsafe { Avx2(_mm256_and_si256(self.0, other.0)) } }
+    #[inline(always)]
+    fn or(self, other: Avx2) -> Avx2 { unsafe { Avx2(_mm256_or_si256(self.0, other.0)) } }
+    #[inline(always)]
+    fn xor(self, other: Avx2) -> Avx2 { unsafe { Avx2(_mm256_xor_si256(self.0, other.0)) } }
+    #[inline(always)]
+    fn not(self) -> Avx2 { self.xor(Avx2::splat(!0)) }
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Avx2 {
+        unsafe {
+            Avx2(_mm256_or_si256(_mm256_sll_epi32(self.0, _mm_cvtsi32_si128(n as i32)),
+                                 _mm256_srl_epi32(self.0, _mm_cvtsi32_si128(32 - n as i32))))
+        }
+    }
+}
+
+const INIT: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+const SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+// floor(|sin(i + 1)| * 2^32)
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
+    0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
+    0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+    0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
+    0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+    0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+// The MD5 state after the one block `m`, starting from the initial state
+#[inline(always)]
+fn compress<W: Word>(m: &[W; 16]) -> [W; 4] {
+    let (mut a, mut b, mut c, mut d) = (W::splat(INIT[0]), W::splat(INIT[1]), W::splat(INIT[2]), W::splat(INIT[3]));
+    macro_rules! rounds {
+        ($round:expr, |$i:ident| $f:expr, $g:expr) => {
+            for $i in $round * 16..$round * 16 + 16 {
+                let f = $f.add(a).add(W::splat(K[$i])).add(m[$g]);
+                a = d;
+                d = c;
+                c = b;
+                b = b.add(f.rotl(SHIFTS[$round * 4 + $i % 4]));
+            }
+        }
+    }
+    rounds!(0, |i| b.and(c).or(b.not().and(d)), i);
+    rounds!(1, |i| d.and(b).or(d.not().and(c)), (5 * i + 1) % 16);
+    rounds!(2, |i| b.xor(c).xor(d), (3 * i + 5) % 16);
+    rounds!(3, |i| c.xor(b.or(d.not())), (7 * i) % 16);
+    [a.add(W::splat(INIT[0])), b.add(W::splat(INIT[1])), c.add(W::splat(INIT[2])), d.add(W::splat(INIT[3]))]
+}
+
+// Hash W::LANES padded blocks, transposing them into and out of the lanes
+#[inline(always)]
+fn hash_blocks<W: Word>(blocks: &[[u32; 16]], states: &mut [[u32; 4]]) {
+    let mut lanes = [0; 8];
+    let mut m = [W::splat(0); 16];
+    for (i, word) in m.iter_mut().enumerate() {
+        for (lane, block) in lanes.iter_mut().zip(blocks) {
+            *lane = block[i];
+        }
+        *word = W::load(&lanes[..W::LANES]);
+    }
+    for (i, word) in compress(&m).iter().enumerate() {
+        word.store(&mut lanes[..W::LANES]);
+        for (state, &lane) in states.iter_mut().zip(&lanes) {
+            state[i] = lane;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_blocks_avx2(blocks: &[[u32; 16]], states: &mut [[u32; 4]]) {
+    hash_blocks::<Avx2>(blocks, states)
+}
+
+fn hash(backend: Backend, blocks: &[[u32; 16]], states: &mut [[u32; 4]]) {
+    match backend {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Sse2 => hash_blocks::<Sse2>(blocks, states),
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => {
+            assert!(backend.available(), "this CPU doesn't have AVX2");
+            unsafe { hash_blocks_avx2(blocks, states) }
+        }
+        _ => for (block, state) in blocks.iter().zip(states.iter_mut()) {
+            hash_blocks::<u32>(::std::slice::from_ref(block), ::std::slice::from_mut(state))
+        },
+    }
+}
+
+// A message of at most MAX_LEN bytes with MD5's padding, as little endian words
+fn pad(message: &[u8]) -> [u32; 16] {
+    assert!(message.len() <= MAX_LEN, "message is over {} bytes", MAX_LEN);
+    let mut bytes = [0; 64];
+    bytes[..message.len()].copy_from_slice(message);
+    bytes[message.len()] = 0x80;
+    bytes[56..].copy_from_slice(&(message.len() as u64 * 8).to_le_bytes());
+    let mut block = [0; 16];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks(4)) {
+        *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+    block
+}
+
+fn digest(state: &[u32; 4]) -> [u8; 16] {
+    let mut digest = [0; 16];
+    for (chunk, word) in digest.chunks_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+fn hex(digest: &[u8; 16]) -> [u8; 32] {
+    const DIGITS: &[u8] = b"0123456789abcdef";
+    let mut hex = [0; 32];
+    for (pair, b) in hex.chunks_mut(2).zip(digest) {
+        pair[0] = DIGITS[(b >> 4) as usize];
+        pair[1] = DIGITS[(b & 0xf) as usize];
+    }
+    hex
+}
+
+// The MD5 digest of each message, each hashed `stretch` more times as a hex
+// string, as Search::hash does
+pub fn digests<M: AsRef<[u8]>>(backend: Backend, messages: &[M], stretch: usize) -> Vec<[u8; 16]> {
+    let lanes = backend.lanes();
+    let mut blocks = vec![[0; 16]; lanes];
+    let mut states = vec![[0; 4]; lanes];
+    let mut digests = Vec::with_capacity(messages.len());
+    for chunk in messages.chunks(lanes) {
+        // spare lanes at the end just hash an empty message
+        for (i, block) in blocks.iter_mut().enumerate() {
+            *block = pad(chunk.get(i).map_or(&[][..], |m| m.as_ref()));
+        }
+        hash(backend, &blocks, &mut states);
+        for _ in 0..stretch {
+            for (block, state) in blocks.iter_mut().zip(&states) {
+                *block = pad(&hex(&digest(state)));
+            }
+            hash(backend, &blocks, &mut states);
+        }
+        digests.extend(states[..chunk.len()].iter().map(digest));
+    }
+    digests
+}
+
+#[test]
+fn test_message() {
+    assert_eq!(Message::new(b"abc", 0).as_ref(), b"abc0");
+    assert_eq!(Message::new(b"abc", 3231929).as_ref(), b"abc3231929");
+}
+
+#[test]
+fn test_digests() {
+    use crypto::digest::Digest;
+    use crypto::md5::Md5;
+
+    let messages = (0..MAX_LEN + 1).map(|n| (0..n).map(|i| (i * 37 + n) as u8).collect::<Vec<_>>())
+                                   .collect::<Vec<_>>();
+    let expected = |stretch| messages.iter().map(|m| {
+        let mut md5 = Md5::new();
+        md5.input(m);
+        for _ in 0..stretch {
+            let hex = md5.result_str();
+            md5.reset();
+            md5.input_str(&hex);
+        }
+        let mut digest = [0; 16];
+        md5.result(&mut digest);
+        digest
+    }).collect::<Vec<_>>();
+    let (plain, stretched) = (expected(0), expected(3));
+    for &backend in [Backend::Scalar, Backend::Sse2, Backend::Avx2].iter().filter(|b| b.available()) {
+        assert_eq!(digests(backend, &messages, 0), plain, "{:?}", backend);
+        assert_eq!(digests(backend, &messages, 3), stretched, "{:?}", backend);
+    }
+}
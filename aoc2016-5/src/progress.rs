@@ -0,0 +1,95 @@
+// Shows a password search's Progress on stderr: a bar redrawn in place when
+// stderr is a terminal, otherwise a log line every few seconds
+
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use Progress;
+
+pub struct Reporter {
+    label: String,
+    tty: bool,
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl Reporter {
+    pub fn new(label: &str) -> Reporter {
+        let tty = io::stderr().is_terminal();
+        Reporter {
+            label: label.to_string(),
+            tty,
+            interval: if tty { Duration::from_millis(100) } else { Duration::from_secs(5) },
+            last: None,
+        }
+    }
+
+    pub fn report(&mut self, progress: &Progress) {
+        if self.last.is_some_and(|t| t.elapsed() < self.interval) {
+            return;
+        }
+        self.last = Some(Instant::now());
+        if self.tty {
+            eprint!("\r\x1b[K{}", bar(&self.label, progress, 30));
+            let _ = io::stderr().flush();
+        } else {
+            eprintln!("{}", line(&self.label, progress));
+        }
+    }
+
+    // Clear the bar so normal output can carry on
+    pub fn finish(&mut self) {
+        if self.tty {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+fn format_rate(rate: f64) -> String {
+    match rate {
+        r if r < 1e3 => format!("{:.0} H/s", r),
+        r if r < 1e6 => format!("{:.1} kH/s", r / 1e3),
+        r => format!("{:.2} MH/s", r / 1e6),
+    }
+}
+
+fn format_eta(progress: &Progress) -> String {
+    match progress.eta() {
+        Some(eta) if eta.as_secs() >= 60 => format!("{}m {:02}s", eta.as_secs() / 60, eta.as_secs() % 60),
+        Some(eta) => format!("{}s", eta.as_secs()),
+        None => "?".to_string(),
+    }
+}
+
+pub fn bar(label: &str, progress: &Progress, width: usize) -> String {
+    let filled = ((progress.fraction() * width as f64) as usize).min(width);
+    format!("{} [{}{}] {} {} searched, {}, ETA {}",
+            label, "#".repeat(filled), "-".repeat(width - filled), progress.password,
+            progress.searched, format_rate(progress.rate()), format_eta(progress))
+}
+
+pub fn line(label: &str, progress: &Progress) -> String {
+    format!("{}: {} after {} searched ({:.0}%), {}, ETA {}",
+            label, progress.password, progress.searched, progress.fraction() * 100.0,
+            format_rate(progress.rate()), format_eta(progress))
+}
+
+// Just the password so far, rate and ETA, for where there's little room
+pub fn summary(progress: &Progress) -> String {
+    format!("{}, {}, ETA {}", progress.password, format_rate(progress.rate()), format_eta(progress))
+}
+
+#[test]
+fn test_bar() {
+    let progress = Progress {
+        searched: 3_000_000,
+        password: "18f4____".to_string(),
+        remaining: 3_000_000.0,
+        elapsed: Duration::from_secs(2),
+    };
+    assert_eq!(bar("part 1", &progress, 10),
+               "part 1 [#####-----] 18f4____ 3000000 searched, 1.50 MH/s, ETA 2s");
+    assert_eq!(line("part 1", &progress),
+               "part 1: 18f4____ after 3000000 searched (50%), 1.50 MH/s, ETA 2s");
+    assert_eq!(summary(&progress), "18f4____, 1.50 MH/s, ETA 2s");
+}
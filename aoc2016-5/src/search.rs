@@ -0,0 +1,223 @@
+// Searching for "interesting" hashes of a salt followed by an increasing
+// index, where interesting means the hex digest starts with some number of
+// zeros. Day 5 wants five zeros, but other MD5 puzzles want more, or hash each
+// digest again a number of times first (key stretching).
+
+use std::any::TypeId;
+use std::ops::Range;
+use std::time::Instant;
+
+use crypto::digest::Digest;
+use crypto::md5::Md5;
+
+use md5x::{self, Backend, Message};
+use Progress;
+
+pub struct Search<T> {
+    hasher: T,
+    salt: String,
+    zeros: usize,
+    stretch: usize,
+    digest: Vec<u8>,
+    // hash several indices at once with md5x, when the hasher is MD5
+    backend: Option<Backend>,
+}
+
+// An index whose hash starts with enough zeros
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub index: usize,
+    pub hex: String,
+    zeros: usize,
+}
+
+impl Hit {
+    // The nth hex digit after the leading zeros
+    pub fn after(&self, n: usize) -> Option<char> {
+        self.hex.chars().nth(self.zeros + n)
+    }
+}
+
+// Whether the first `nibbles` hex digits of `hash` are zero
+pub fn leading_zeros(hash: &[u8], nibbles: usize) -> bool {
+    hash[..nibbles / 2].iter().all(|&b| b == 0) &&
+        (nibbles.is_multiple_of(2) || hash[nibbles / 2] < 0x10)
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl<T: Digest + 'static> Search<T> {
+    // Five leading zeros and no stretching, as in day 5
+    pub fn new(hasher: T, salt: &str) -> Search<T> {
+        let digest = vec![0; hasher.output_bytes()];
+        let backend = if TypeId::of::<T>() == TypeId::of::<Md5>() { Some(Backend::detect()) } else { None };
+        Search { hasher, salt: salt.to_string(), zeros: 5, stretch: 0, digest, backend }
+    }
+
+    pub fn zeros(mut self, zeros: usize) -> Search<T> {
+        assert!(zeros <= self.digest.len() * 2, "a digest only has {} hex digits", self.digest.len() * 2);
+        self.zeros = zeros;
+        self
+    }
+
+    // Hash the hex digest this many more times
+    pub fn stretch(mut self, stretch: usize) -> Search<T> {
+        self.stretch = stretch;
+        self
+    }
+
+    // Which md5x backend chunk() uses, or None to use the hasher one index
+    // at a time. Only MD5 searches can use md5x.
+    pub fn backend(mut self, backend: Option<Backend>) -> Search<T> {
+        assert!(backend.is_none() || TypeId::of::<T>() == TypeId::of::<Md5>(), "md5x only does MD5");
+        assert!(backend.is_none_or(Backend::available), "this CPU can't use {:?}", backend);
+        self.backend = backend;
+        self
+    }
+
+    pub fn salt(&self) -> &str {
+        &self.salt
+    }
+
+    // About how many indices there are between hits
+    pub fn hit_rate(&self) -> f64 {
+        16f64.powi(self.zeros as i32)
+    }
+
+    pub fn hash(&mut self, index: usize) -> &[u8] {
+        self.hasher.reset();
+        self.hasher.input_str(&format!("{}{}", self.salt, index));
+        for _ in 0..self.stretch {
+            let hex = self.hasher.result_str();
+            self.hasher.reset();
+            self.hasher.input_str(&hex);
+        }
+        self.hasher.result(&mut self.digest);
+        &self.digest
+    }
+
+    // Every hit in a range of indices
+    pub fn chunk(&mut self, range: Range<usize>) -> Vec<Hit> {
+        // an index has at most 20 digits
+        if let Some(backend) = self.backend.filter(|_| self.salt.len() + 20 <= md5x::MAX_LEN) {
+            let messages = range.clone().map(|i| Message::new(self.salt.as_bytes(), i)).collect::<Vec<_>>();
+            let zeros = self.zeros;
+            return range.zip(md5x::digests(backend, &messages, self.stretch))
+                        .filter(|(_, digest)| leading_zeros(digest, zeros))
+                        .map(|(index, digest)| Hit { index, hex: hex(&digest), zeros })
+                        .collect();
+        }
+        let mut hits = Vec::new();
+        for index in range {
+            let zeros = self.zeros;
+            if leading_zeros(self.hash(index), zeros) {
+                hits.push(Hit { index, hex: hex(&self.digest), zeros });
+            }
+        }
+        hits
+    }
+}
+
+// How a password is built from the hits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Extract {
+    // the first digit after the zeros is the next character (day 5 part 1)
+    InOrder,
+    // the first digit after the zeros is a position, and the second is the
+    // character for it if that position is still empty (day 5 part 2)
+    Positioned,
+}
+
+// A password being filled in from hits, which must be added in index order
+#[derive(Debug, Clone)]
+pub struct Password {
+    chars: Vec<Option<char>>,
+    extract: Extract,
+}
+
+impl Password {
+    pub fn new(length: usize, extract: Extract) -> Password {
+        assert!(extract == Extract::InOrder || length <= 16, "positions are a single hex digit");
+        Password { chars: vec![None; length], extract }
+    }
+
+    pub fn add(&mut self, hit: &Hit) {
+        let (pos, chr) = match self.extract {
+            Extract::InOrder => (self.chars.iter().position(Option::is_none), hit.after(0)),
+            Extract::Positioned => (hit.after(0).and_then(|c| c.to_digit(16)).map(|p| p as usize)
+                                       .filter(|&p| p < self.chars.len() && self.chars[p].is_none()),
+                                    hit.after(1)),
+        };
+        if let (Some(pos), Some(chr)) = (pos, chr) {
+            self.chars[pos] = Some(chr);
+        }
+    }
+
+    // About how many more hits it takes to finish
+    pub fn hits_left(&self) -> f64 {
+        let left = self.chars.iter().filter(|c| c.is_none()).count();
+        // a positioned hit lands on any of 16 positions, so filling the last
+        // k free ones takes 16/k + 16/(k-1) + ... + 16/1 hits on average
+        match self.extract {
+            Extract::InOrder => left as f64,
+            Extract::Positioned => (1..left + 1).map(|k| 16.0 / k as f64).sum(),
+        }
+    }
+
+    // The password so far, with '_' for characters not found yet
+    pub fn so_far(&self) -> String {
+        self.chars.iter().map(|c| c.unwrap_or('_')).collect()
+    }
+
+    pub fn finished(&self) -> Option<String> {
+        self.chars.iter().cloned().collect()
+    }
+}
+
+// How many indices are searched between progress reports
+pub const CHUNK_SIZE: usize = 10_000;
+
+// Build a `length` character password from hits, searching chunks of indices
+// and passing the progress to `report` after each
+pub fn find_password<T, F>(search: &mut Search<T>, length: usize, extract: Extract, mut report: F) -> String
+    where T: Digest + 'static, F: FnMut(&Progress)
+{
+    let start = Instant::now();
+    let mut password = Password::new(length, extract);
+    let mut index = 0;
+    loop {
+        for hit in search.chunk(index..index + CHUNK_SIZE) {
+            password.add(&hit);
+        }
+        index += CHUNK_SIZE;
+        if let Some(password) = password.finished() {
+            return password;
+        }
+        report(&Progress {
+            searched: index,
+            password: password.so_far(),
+            remaining: password.hits_left() * search.hit_rate(),
+            elapsed: start.elapsed(),
+        });
+    }
+}
+
+#[test]
+fn test_zeros() {
+    assert!(leading_zeros(&[0, 0, 0x0f, 0xff], 5));
+    assert!(!leading_zeros(&[0, 0, 0x10, 0xff], 5));
+    assert!(leading_zeros(&[0, 0, 0, 0xff], 6));
+    // the 2015 day 4 example
+    assert_eq!(Search::new(Md5::new(), "abcdef").chunk(609_000..610_000)[0].index, 609_043);
+    assert!(Search::new(Md5::new(), "abcdef").zeros(6).chunk(609_043..609_044).is_empty());
+}
+
+#[test]
+fn test_stretch() {
+    // the 2016 day 14 example
+    let mut search = Search::new(Md5::new(), "abc").stretch(2016);
+    let hex = search.hash(0).iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    assert_eq!(hex, "a107ff634856bb300138cac6568c0f24");
+}
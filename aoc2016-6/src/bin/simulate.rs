@@ -0,0 +1,52 @@
+extern crate aoc2016_6;
+
+use std::env;
+use std::process;
+
+use aoc2016_6::channel::{simulate, recovery_rate, Noise};
+
+fn usage() -> ! {
+    let name = env::args().next().unwrap();
+    println!("Usage: {} [--trials N] message repeats noise [seed]", &name);
+    println!("  noise is one of uniform:RATE, burst:RATE:LENGTH or inverted:DECOYS");
+    println!("  with --trials, prints how often the message is recovered instead");
+    process::exit(1);
+}
+
+fn main() {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let trials = if args.first().map(|a| a == "--trials") == Some(true) {
+        if args.len() < 2 { usage(); }
+        let t = args[1].parse::<usize>().unwrap_or_else(|_| usage());
+        args.drain(0..2);
+        Some(t)
+    } else {
+        None
+    };
+    if args.len() < 3 || args.len() > 4 { usage(); }
+
+    let message = &args[0];
+    let repeats = args[1].parse::<usize>().unwrap_or_else(|_| usage());
+    let noise = match args[2].parse::<Noise>() {
+        Ok(n) => n,
+        Err(e) => { println!("{}", e); usage(); }
+    };
+    let seed = match args.get(3) {
+        Some(s) => s.parse::<u64>().unwrap_or_else(|_| usage()),
+        None => 0,
+    };
+
+    let result = match trials {
+        Some(t) => recovery_rate(message, repeats, noise, seed, t).map(|rate| {
+            println!("Recovered '{}' in {:.1}% of {} trials ({}, {} repeats)",
+                     message, rate * 100.0, t, noise, repeats);
+        }),
+        None => simulate(message, repeats, noise, seed).map(|lines| {
+            for line in lines { println!("{}", line); }
+        }),
+    };
+    if let Err(e) = result {
+        println!("{}", e);
+        process::exit(1);
+    }
+}
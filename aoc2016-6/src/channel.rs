@@ -0,0 +1,215 @@
+// Simulates a jammed repetition-code channel, producing recordings in the same
+// format as input.txt. Everything is driven by a seeded generator so the same
+// arguments always give the same recording.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::{decode_least_common, decode_most_common};
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Noise {
+    // every character is independently replaced with probability `rate`
+    Uniform { rate: f64 },
+    // a run of `length` replaced characters starts at each position with
+    // probability `rate`. Runs carry on into the next line.
+    Burst { rate: f64, length: usize },
+    // the real character is the least common one in each column (part 2),
+    // hidden amongst `decoys` more common letters
+    Inverted { decoys: usize },
+}
+
+impl Noise {
+    // The inverted model needs the part 2 decoder, everything else part 1
+    pub fn decode(&self, recording: &str) -> String {
+        match *self {
+            Noise::Inverted { .. } => decode_least_common(recording),
+            _ => decode_most_common(recording),
+        }
+    }
+}
+
+// Parses "uniform:RATE", "burst:RATE:LENGTH" or "inverted:DECOYS"
+impl FromStr for Noise {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Noise, String> {
+        let parts = s.split(':').collect::<Vec<_>>();
+        let rate = |text: &str| match text.parse::<f64>() {
+            Ok(r) if (0.0..=1.0).contains(&r) => Ok(r),
+            _ => Err(format!("Invalid rate '{}' in '{}'", text, s)),
+        };
+        let count = |text: &str| text.parse::<usize>()
+                                     .map_err(|_| format!("Invalid count '{}' in '{}'", text, s));
+        match parts[..] {
+            ["uniform", r] => Ok(Noise::Uniform { rate: rate(r)? }),
+            ["burst", r, l] => Ok(Noise::Burst { rate: rate(r)?, length: count(l)? }),
+            ["inverted", d] => Ok(Noise::Inverted { decoys: count(d)? }),
+            _ => Err(format!("Unknown noise model '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for Noise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Noise::Uniform { rate } => write!(f, "uniform:{}", rate),
+            Noise::Burst { rate, length } => write!(f, "burst:{}:{}", rate, length),
+            Noise::Inverted { decoys } => write!(f, "inverted:{}", decoys),
+        }
+    }
+}
+
+// xorshift64*. Not cryptographic, but stable across platforms and crate
+// versions, which is what matters for fixtures.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // zero is a fixed point of xorshift, so mix the seed and never start
+        // there, even for the one seed that mixes to zero
+        match seed ^ 0x9e37_79b9_7f4a_7c15 {
+            0 => Rng(0x9e37_79b9_7f4a_7c15),
+            state => Rng(state),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // uniform in [0, n)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // a random letter that isn't `not`
+    fn other_letter(&mut self, not: u8) -> u8 {
+        let c = ALPHABET[self.below(ALPHABET.len() - 1)];
+        if c >= not { c + 1 } else { c }
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+// Send `message` `repeats` times through the channel, returning one recorded
+// line per repeat
+pub fn simulate(message: &str, repeats: usize, noise: Noise, seed: u64)
+    -> Result<Vec<String>, String> {
+    if message.is_empty() || !message.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(format!("Message must be lowercase letters, got '{}'", message));
+    }
+    if repeats == 0 {
+        return Err("Need at least one repeat".to_string());
+    }
+    let mut rng = Rng::new(seed);
+    let clean = message.as_bytes();
+    let mut lines = vec![clean.to_vec(); repeats];
+    match noise {
+        Noise::Uniform { rate } => {
+            for c in lines.iter_mut().flat_map(|l| l.iter_mut()) {
+                if rng.next_f64() < rate { *c = rng.other_letter(*c); }
+            }
+        }
+        Noise::Burst { rate, length } => {
+            let mut remaining = 0;
+            for c in lines.iter_mut().flat_map(|l| l.iter_mut()) {
+                if remaining == 0 && rng.next_f64() < rate { remaining = length; }
+                if remaining > 0 {
+                    *c = rng.other_letter(*c);
+                    remaining -= 1;
+                }
+            }
+        }
+        Noise::Inverted { decoys } => {
+            // the real letter appears `real` times and every decoy at least once
+            // more, so we need room for that in each column
+            if decoys == 0 || decoys > ALPHABET.len() - 1 {
+                return Err(format!("Need between 1 and 25 decoys, got {}", decoys));
+            }
+            if repeats < 2 * decoys + 1 {
+                return Err(format!("{} decoys need at least {} repeats", decoys, 2 * decoys + 1));
+            }
+            let real = (repeats - decoys) / (decoys + 1);
+            for (pos, &letter) in clean.iter().enumerate() {
+                let mut pool = ALPHABET.iter().cloned().filter(|&c| c != letter).collect::<Vec<_>>();
+                rng.shuffle(&mut pool);
+                let mut counts = vec![real + 1; decoys];
+                for _ in 0..repeats - real - decoys * (real + 1) {
+                    counts[rng.below(decoys)] += 1;
+                }
+                let mut column = vec![letter; real];
+                for (&decoy, &n) in pool.iter().zip(counts.iter()) {
+                    column.extend(std::iter::repeat_n(decoy, n));
+                }
+                rng.shuffle(&mut column);
+                for (line, c) in lines.iter_mut().zip(column) {
+                    line[pos] = c;
+                }
+            }
+        }
+    }
+    Ok(lines.into_iter().map(|l| String::from_utf8(l).unwrap()).collect())
+}
+
+// Fraction of `trials` simulated recordings that decode back to `message`
+pub fn recovery_rate(message: &str, repeats: usize, noise: Noise, seed: u64, trials: usize)
+    -> Result<f64, String> {
+    if trials == 0 {
+        return Err("Need at least one trial".to_string());
+    }
+    let mut recovered = 0;
+    for trial in 0..trials as u64 {
+        let recording = simulate(message, repeats, noise, seed.wrapping_add(trial))?.join("\n");
+        if noise.decode(&recording) == message { recovered += 1; }
+    }
+    Ok(recovered as f64 / trials as f64)
+}
+
+#[test]
+fn test_deterministic() {
+    let noise = Noise::Uniform { rate: 0.5 };
+    assert_eq!(simulate("easter", 20, noise, 7), simulate("easter", 20, noise, 7));
+    assert!(simulate("easter", 20, noise, 7) != simulate("easter", 20, noise, 8));
+}
+
+#[test]
+fn test_decodes() {
+    for noise in &["uniform:0.3", "burst:0.05:6", "inverted:3"] {
+        let noise = noise.parse::<Noise>().unwrap();
+        assert_eq!(recovery_rate("advent", 200, noise, 1, 5), Ok(1.0), "{}", noise);
+    }
+}
+
+#[test]
+fn test_rng() {
+    for &seed in &[0, 0x9e37_79b9_7f4a_7c15] {
+        let mut rng = Rng::new(seed);
+        assert!((0..4).map(|_| rng.next_u64()).any(|n| n != 0), "seed {} only gives zeros", seed);
+    }
+}
+
+#[test]
+fn test_bad_args() {
+    assert!(simulate("Easter", 10, Noise::Uniform { rate: 0.1 }, 0).is_err());
+    assert!(simulate("easter", 4, Noise::Inverted { decoys: 2 }, 0).is_err());
+    assert_eq!(recovery_rate("easter", 10, Noise::Uniform { rate: 0.1 }, 0, 0),
+               Err("Need at least one trial".to_string()));
+    assert!("uniform:1.5".parse::<Noise>().is_err());
+    assert!("gaussian:0.1".parse::<Noise>().is_err());
+}
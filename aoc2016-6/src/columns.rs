@@ -0,0 +1,165 @@
+// Streaming per-column character counts. Only the counts are kept, so memory
+// is proportional to the message width times the alphabet size no matter how
+// long the recording is.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+// What to do with a line that isn't the same width as the first one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ragged {
+    // stop with an error naming the line
+    Error,
+    // count whatever characters the line has. Longer lines widen the message.
+    Pad,
+    // skip the line entirely
+    Ignore,
+}
+
+impl FromStr for Ragged {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Ragged, String> {
+        match s {
+            "error" => Ok(Ragged::Error),
+            "pad" => Ok(Ragged::Pad),
+            "ignore" => Ok(Ragged::Ignore),
+            _ => Err(format!("Unknown ragged line policy '{}'", s)),
+        }
+    }
+}
+
+pub struct Columns {
+    // BTreeMap keeps each column in character order, which gives alphabetical
+    // tie breaking for free
+    counts: Vec<BTreeMap<char, usize>>,
+    width: Option<usize>,
+    ragged: Ragged,
+    non_ascii: bool,
+    lines: usize,
+    skipped: usize,
+}
+
+impl Columns {
+    pub fn new(ragged: Ragged) -> Columns {
+        Columns {
+            counts: Vec::new(),
+            width: None,
+            ragged,
+            non_ascii: false,
+            lines: 0,
+            skipped: 0,
+        }
+    }
+
+    // Non-ASCII characters are rejected unless this is set, in which case each
+    // unicode scalar value counts as one symbol
+    pub fn allow_non_ascii(mut self, allow: bool) -> Columns {
+        self.non_ascii = allow;
+        self
+    }
+
+    // Add one recorded line. Surrounding whitespace is ignored and blank lines
+    // are skipped.
+    pub fn add_line(&mut self, line: &str) -> Result<(), String> {
+        self.lines += 1;
+        let line = line.trim();
+        if line.is_empty() { return Ok(()); }
+        if !self.non_ascii {
+            if let Some(pos) = line.chars().position(|c| !c.is_ascii()) {
+                return Err(format!("Non-ASCII character at line {}, column {}",
+                                   self.lines, pos + 1));
+            }
+        }
+        let len = line.chars().count();
+        match self.width {
+            None => self.width = Some(len),
+            Some(w) if w == len => {},
+            Some(w) => match self.ragged {
+                Ragged::Error => {
+                    return Err(format!("Line {} has {} characters, expected {}",
+                                       self.lines, len, w));
+                }
+                Ragged::Ignore => {
+                    self.skipped += 1;
+                    return Ok(());
+                }
+                Ragged::Pad => if len > w { self.width = Some(len) },
+            },
+        }
+        for (index, c) in line.chars().enumerate() {
+            if self.counts.len() <= index {
+                self.counts.push(BTreeMap::new());
+            }
+            *self.counts[index].entry(c).or_insert(0) += 1;
+        }
+        Ok(())
+    }
+
+    // Add every line from a reader, one at a time
+    pub fn read<R: BufRead>(&mut self, mut reader: R) -> Result<(), String> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => return Ok(()),
+                Ok(_) => self.add_line(&line)?,
+                Err(e) => return Err(format!("Error reading line {}: {}", self.lines + 1, e)),
+            }
+        }
+    }
+
+    // Number of lines skipped by Ragged::Ignore
+    pub fn skipped(&self) -> usize { self.skipped }
+
+    pub fn counts(&self) -> &[BTreeMap<char, usize>] { &self.counts }
+
+    fn pick<F>(&self, better: F) -> String
+        where F: Fn(usize, usize) -> bool
+    {
+        self.counts.iter().map(|column| {
+            let mut best = column.iter().next().unwrap();
+            for entry in column {
+                if better(*entry.1, *best.1) { best = entry; }
+            }
+            *best.0
+        }).collect()
+    }
+
+    // The most common character in each column (part 1)
+    pub fn most_common(&self) -> String {
+        self.pick(|count, best| count > best)
+    }
+
+    // The least common character in each column (part 2)
+    pub fn least_common(&self) -> String {
+        self.pick(|count, best| count < best)
+    }
+}
+
+#[test]
+fn test_ragged() {
+    let text = "abc\nabcd\nab\nxbc\n";
+    let mut strict = Columns::new(Ragged::Error);
+    assert_eq!(strict.read(text.as_bytes()), Err("Line 2 has 4 characters, expected 3".to_string()));
+
+    let mut padded = Columns::new(Ragged::Pad);
+    padded.read(text.as_bytes()).unwrap();
+    assert_eq!(padded.most_common(), "abcd");
+
+    let mut ignored = Columns::new(Ragged::Ignore);
+    ignored.read(text.as_bytes()).unwrap();
+    assert_eq!(ignored.most_common(), "abc");
+    assert_eq!(ignored.skipped(), 2);
+}
+
+#[test]
+fn test_non_ascii() {
+    let text = "añb\nañb\nacb\n";
+    assert!(Columns::new(Ragged::Error).read(text.as_bytes()).is_err());
+    let mut columns = Columns::new(Ragged::Error).allow_non_ascii(true);
+    columns.read(text.as_bytes()).unwrap();
+    assert_eq!(columns.most_common(), "añb");
+    assert_eq!(columns.least_common(), "acb");
+}
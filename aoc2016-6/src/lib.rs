@@ -0,0 +1,33 @@
+pub mod channel;
+pub mod columns;
+
+use columns::{Columns, Ragged};
+
+// Count a whole recording held in memory. Ragged lines are padded and any
+// character is accepted, so this can't fail.
+fn count_all(text: &str) -> Columns {
+    let mut columns = Columns::new(Ragged::Pad).allow_non_ascii(true);
+    for line in text.lines() {
+        columns.add_line(line).unwrap();
+    }
+    columns
+}
+
+// The most common character in each column (part 1)
+pub fn decode_most_common(text: &str) -> String {
+    count_all(text).most_common()
+}
+
+// The least common character in each column (part 2)
+pub fn decode_least_common(text: &str) -> String {
+    count_all(text).least_common()
+}
+
+#[test]
+fn test_example() {
+    let recording =
+        "eedadn\ndrvtee\neandsr\nraavrd\natevrs\ntsrnev\nsdttsa\nrasrtv\n\
+         nssdts\nntnada\nsvetve\ntesnvt\nvntsnd\nvrdear\ndvrsen\nenarar";
+    assert_eq!(decode_most_common(recording), "easter");
+    assert_eq!(decode_least_common(recording), "advent");
+}
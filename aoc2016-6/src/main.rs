@@ -1,3 +1,65 @@
+extern crate aoc2016_6;
+
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::process;
+
+use aoc2016_6::columns::{Columns, Ragged};
+
+fn usage() -> ! {
+    let name = env::args().next().unwrap();
+    println!("Usage: {} [--ragged error|pad|ignore] [--non-ascii] input-filename", &name);
+    println!("  use - as the filename to read the recording from stdin");
+    process::exit(1);
+}
+
 fn main() {
-    println!("Hello, world!");
+    let mut ragged = Ragged::Error;
+    let mut non_ascii = false;
+    let mut fname = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ragged" => {
+                ragged = match args.next().map(|a| a.parse::<Ragged>()) {
+                    Some(Ok(r)) => r,
+                    Some(Err(e)) => { println!("{}", e); usage(); }
+                    None => usage(),
+                }
+            }
+            "--non-ascii" => non_ascii = true,
+            _ if fname.is_none() => fname = Some(arg),
+            _ => usage(),
+        }
+    }
+    let fname = fname.unwrap_or_else(|| usage());
+
+    // count the recording a line at a time rather than reading it all in
+    let mut columns = Columns::new(ragged).allow_non_ascii(non_ascii);
+    let result = if fname == "-" {
+        let stdin = io::stdin();
+        let lock = stdin.lock();
+        columns.read(lock)
+    } else {
+        match File::open(&fname) {
+            Ok(f) => columns.read(BufReader::new(f)),
+            Err(e) => Err(format!("Couldn't open file '{}': {}", &fname, e)),
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            if columns.skipped() > 0 {
+                println!("Ignored {} ragged lines", columns.skipped());
+            }
+            println!("Error-corrected message (part1): {}", columns.most_common());
+            println!("Error-corrected message (part2): {}", columns.least_common());
+        }
+        Err(e) => {
+            println!("{}", e);
+            process::exit(1);
+        }
+    }
 }